@@ -12,6 +12,15 @@ use std::{fmt, io};
 /// are safe to use in this context. However, using these types with
 /// `AllowStdIo` will cause the event loop to block, so they should be used
 /// with care.
+///
+/// If the wrapped type implements `std::io::BufRead`, so does `AllowStdIo`.
+/// This allows a synchronous buffered reader, such as `std::io::BufReader`
+/// over a file, to be used with the buffered helpers in this module like
+/// [`read_until`] or [`lines`]. Refilling the buffer performs a blocking read
+/// in that case as well.
+///
+/// [`read_until`]: fn.read_until.html
+/// [`lines`]: fn.lines.html
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct AllowStdIo<T>(T);
 
@@ -79,3 +88,18 @@ impl<T> AsyncRead for AllowStdIo<T> where T: io::Read {
     // TODO: override prepare_unitialized_buffer once `Read::initializer` is stable.
     // See rust-lang/rust #42788
 }
+
+impl<T> io::BufRead for AllowStdIo<T> where T: io::BufRead {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.0.fill_buf()
+    }
+    fn consume(&mut self, amt: usize) {
+        self.0.consume(amt)
+    }
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> io::Result<usize> {
+        self.0.read_until(byte, buf)
+    }
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.0.read_line(buf)
+    }
+}
@@ -0,0 +1,62 @@
+use std::io as std_io;
+use futures::Poll;
+
+use AsyncWrite;
+
+/// Writes bytes asynchronously into a buffer owned by the writer.
+///
+/// This is the write side counterpart of `BufRead`: rather than copying data
+/// out of a caller supplied slice, the writer lends out the unused part of
+/// its buffer through [`poll_write_buf`], the caller writes or encodes data
+/// into it, and then commits the bytes it filled with [`advance`]. Committed
+/// data is written out like data passed to `write`, e.g. once the buffer is
+/// full or the writer is flushed.
+///
+/// [`poll_write_buf`]: #tymethod.poll_write_buf
+/// [`advance`]: #tymethod.advance
+pub trait AsyncBufWrite: AsyncWrite {
+    /// Attempts to return the unused part of the internal buffer, writing out
+    /// buffered data first if there's no room left.
+    ///
+    /// On success the returned slice is never empty. Its contents are
+    /// unspecified, and nothing is written out until some of it is committed
+    /// with `advance`.
+    ///
+    /// If the buffer is full and can't be written out yet, the method returns
+    /// `Ok(Async::NotReady)` and arranges for the current task to receive a
+    /// notification when the writer becomes writable.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if not called within the context of a future's
+    /// task.
+    fn poll_write_buf(&mut self) -> Poll<&mut [u8], std_io::Error>;
+
+    /// Commits the first `n` bytes of the slice returned by the last call to
+    /// `poll_write_buf` as written.
+    ///
+    /// # Panics
+    ///
+    /// Implementations may panic if `n` is larger than that slice.
+    fn advance(&mut self, n: usize);
+}
+
+impl<T: ?Sized + AsyncBufWrite> AsyncBufWrite for Box<T> {
+    fn poll_write_buf(&mut self) -> Poll<&mut [u8], std_io::Error> {
+        (**self).poll_write_buf()
+    }
+
+    fn advance(&mut self, n: usize) {
+        (**self).advance(n)
+    }
+}
+
+impl<'a, T: ?Sized + AsyncBufWrite> AsyncBufWrite for &'a mut T {
+    fn poll_write_buf(&mut self) -> Poll<&mut [u8], std_io::Error> {
+        (**self).poll_write_buf()
+    }
+
+    fn advance(&mut self, n: usize) {
+        (**self).advance(n)
+    }
+}
@@ -3,6 +3,7 @@ use bytes::BufMut;
 use futures::{Async, Poll};
 
 use {framed, split, AsyncWrite};
+use read_buf::ReadBuf;
 use codec::{Decoder, Encoder, Framed};
 use split::{ReadHalf, WriteHalf};
 
@@ -112,6 +113,56 @@ pub trait AsyncRead: std_io::Read {
         }
     }
 
+    /// Attempt to read from the `AsyncRead` into the unfilled part of a
+    /// `ReadBuf`, returning how many bytes were read.
+    ///
+    /// The bytes read are appended to the filled part of `buf`.
+    ///
+    /// The default implementation reads with `poll_read`, so existing
+    /// implementations of `AsyncRead` support this without changes. Memory
+    /// of `buf` which hasn't been initialized yet is passed to
+    /// `prepare_uninitialized_buffer` first. If that zeroed it, it's tracked
+    /// as initialized afterwards, so reusing the buffer doesn't prepare it
+    /// again. Implementations which can read into uninitialized memory
+    /// directly may override this method.
+    fn poll_read_buf(&mut self, buf: &mut ReadBuf) -> Poll<usize, std_io::Error> {
+        unsafe {
+            let initialized = buf.initialized_len() - buf.filled().len();
+            let prepared = {
+                let uninit = &mut buf.unfilled_mut()[initialized..];
+                !uninit.is_empty() && self.prepare_uninitialized_buffer(uninit)
+            };
+            if prepared {
+                let len = buf.remaining();
+                buf.assume_init(len);
+            }
+
+            let n = try_ready!(self.poll_read(buf.unfilled_mut()));
+            buf.assume_init(n);
+            buf.add_filled(n);
+            Ok(Async::Ready(n))
+        }
+    }
+
+    /// Attempt to read from the `AsyncRead` into several buffers, filling
+    /// them in order.
+    ///
+    /// On success, returns `Ok(Async::Ready(num_bytes_read))`. Bytes are
+    /// always written to a buffer before moving on to the next one, so only
+    /// the last buffer touched may be partially filled.
+    ///
+    /// The default implementation calls `poll_read` once, with the first
+    /// non-empty buffer, like `std::io::Read::read_vectored` does. That way
+    /// a `WouldBlock` or an error is never hit after data was already read.
+    /// I/O objects which support scatter reads natively, such as sockets,
+    /// can override this to read into all buffers with a single system call.
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut [u8]]) -> Poll<usize, std_io::Error> {
+        match bufs.iter_mut().find(|buf| !buf.is_empty()) {
+            Some(buf) => self.poll_read(buf),
+            None => Ok(Async::Ready(0)),
+        }
+    }
+
     /// Provides a `Stream` and `Sink` interface for reading and writing to this
     /// `Io` object, using `Decode` and `Encode` to read and write the raw data.
     ///
@@ -150,14 +201,32 @@ impl<T: ?Sized + AsyncRead> AsyncRead for Box<T> {
     unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
         (**self).prepare_uninitialized_buffer(buf)
     }
+
+    fn poll_read_buf(&mut self, buf: &mut ReadBuf) -> Poll<usize, std_io::Error> {
+        (**self).poll_read_buf(buf)
+    }
+
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut [u8]]) -> Poll<usize, std_io::Error> {
+        (**self).poll_read_vectored(bufs)
+    }
 }
 
 impl<'a, T: ?Sized + AsyncRead> AsyncRead for &'a mut T {
     unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
         (**self).prepare_uninitialized_buffer(buf)
     }
+
+    fn poll_read_buf(&mut self, buf: &mut ReadBuf) -> Poll<usize, std_io::Error> {
+        (**self).poll_read_buf(buf)
+    }
+
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut [u8]]) -> Poll<usize, std_io::Error> {
+        (**self).poll_read_vectored(bufs)
+    }
 }
 
+/// Byte slices never block and, since `std` implements `BufRead` for them,
+/// can be used directly with the buffered helpers in the `io` module.
 impl<'a> AsyncRead for &'a [u8] {
     unsafe fn prepare_uninitialized_buffer(&self, _buf: &mut [u8]) -> bool {
         false
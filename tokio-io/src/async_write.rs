@@ -149,11 +149,28 @@ pub trait AsyncWrite: std_io::Write {
 }
 
 impl<T: ?Sized + AsyncWrite> AsyncWrite for Box<T> {
+    fn poll_write(&mut self, buf: &[u8]) -> Poll<usize, std_io::Error> {
+        (**self).poll_write(buf)
+    }
+
+    fn poll_flush(&mut self) -> Poll<(), std_io::Error> {
+        (**self).poll_flush()
+    }
+
     fn shutdown(&mut self) -> Poll<(), std_io::Error> {
         (**self).shutdown()
     }
 }
+
 impl<'a, T: ?Sized + AsyncWrite> AsyncWrite for &'a mut T {
+    fn poll_write(&mut self, buf: &[u8]) -> Poll<usize, std_io::Error> {
+        (**self).poll_write(buf)
+    }
+
+    fn poll_flush(&mut self) -> Poll<(), std_io::Error> {
+        (**self).poll_flush()
+    }
+
     fn shutdown(&mut self) -> Poll<(), std_io::Error> {
         (**self).shutdown()
     }
@@ -171,17 +188,23 @@ impl AsyncWrite for std_io::Sink {
     }
 }
 
-// TODO: Implement `prepare_uninitialized_buffer` for `io::Take`.
-// This is blocked on rust-lang/rust#27269
 impl<T: AsyncRead> AsyncRead for std_io::Take<T> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.get_ref().prepare_uninitialized_buffer(buf)
+    }
 }
 
-// TODO: Implement `prepare_uninitialized_buffer` when upstream exposes inner
-// parts
 impl<T, U> AsyncRead for std_io::Chain<T, U>
     where T: AsyncRead,
           U: AsyncRead,
 {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        // The buffer may be handed to either reader, so it has to be prepared
+        // for both unless the first one already zeroed it.
+        let (first, second) = self.get_ref();
+        first.prepare_uninitialized_buffer(buf) ||
+            second.prepare_uninitialized_buffer(buf)
+    }
 }
 
 impl<T: AsyncWrite> AsyncWrite for std_io::BufWriter<T> {
@@ -198,6 +221,9 @@ impl<T: AsyncRead> AsyncRead for std_io::BufReader<T> {
 }
 
 impl<T: AsRef<[u8]>> AsyncRead for std_io::Cursor<T> {
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
 }
 
 impl<'a> AsyncWrite for std_io::Cursor<&'a mut [u8]> {
@@ -0,0 +1,313 @@
+use std::{cmp, fmt, mem, thread};
+use std::io::{self, BufRead, Read, Write};
+use std::sync::mpsc;
+
+use futures::{Async, Future, Poll};
+use futures::sync::oneshot;
+
+use {AsyncRead, AsyncWrite};
+
+const BUF_LEN: usize = 8 * 1024;
+
+/// A reader which reads from a blocking `Read` on a background thread.
+///
+/// Created by the [`blocking_read`] function.
+///
+/// [`blocking_read`]: fn.blocking_read.html
+pub struct BlockingRead<R: Read + Send + 'static> {
+    // The reader until it's moved to the background thread
+    reader: Option<R>,
+    buf: Vec<u8>,
+    pos: usize,
+    worker: Option<mpsc::Sender<(Vec<u8>, oneshot::Sender<ReadDone>)>>,
+    in_flight: Option<oneshot::Receiver<ReadDone>>,
+}
+
+/// A writer which writes to a blocking `Write` on a background thread.
+///
+/// Created by the [`blocking_write`] function.
+///
+/// [`blocking_write`]: fn.blocking_write.html
+pub struct BlockingWrite<W: Write + Send + 'static> {
+    // The writer until it's moved to the background thread
+    writer: Option<W>,
+    buf: Vec<u8>,
+    // An empty buffer to swap in while `buf` is written out
+    spare: Vec<u8>,
+    worker: Option<mpsc::Sender<(Vec<u8>, oneshot::Sender<WriteDone>)>>,
+    in_flight: Option<oneshot::Receiver<WriteDone>>,
+}
+
+// The result of a read on the background thread, along with the buffer read
+// into.
+type ReadDone = (Vec<u8>, io::Result<usize>);
+
+// The result of writing out a buffer on the background thread, along with
+// the buffer so its allocation can be reused.
+type WriteDone = (Vec<u8>, io::Result<()>);
+
+/// Creates a reader implementing `AsyncRead` and `BufRead` which reads from
+/// `reader`, a blocking `Read`, on a background thread.
+///
+/// Reads are done up to 8KiB at a time by a background thread, which is
+/// started on the first read and exits once the `BlockingRead` is dropped.
+/// Data is only read from `reader` when the buffer has been consumed, so no
+/// more is taken from it than requested. A read which is in progress when
+/// the `BlockingRead` is dropped can't be cancelled though, and its data is
+/// lost.
+///
+/// Reads return 0 bytes whenever `reader` does, and ask it for more data on
+/// the next read.
+pub fn blocking_read<R>(reader: R) -> BlockingRead<R>
+    where R: Read + Send + 'static,
+{
+    BlockingRead {
+        reader: Some(reader),
+        buf: Vec::new(),
+        pos: 0,
+        worker: None,
+        in_flight: None,
+    }
+}
+
+/// Creates a writer implementing `AsyncWrite` which writes to `writer`, a
+/// blocking `Write`, on a background thread.
+///
+/// Data written is collected in a buffer of 8KiB and handed to the
+/// background thread once the buffer is full or the writer is flushed, where
+/// it's written to `writer` with `write_all`, followed by a `flush`. The
+/// thread is started on the first write out and exits once the
+/// `BlockingWrite` is dropped. Data still buffered when it's dropped is
+/// written out in the background, without waiting for it.
+pub fn blocking_write<W>(writer: W) -> BlockingWrite<W>
+    where W: Write + Send + 'static,
+{
+    BlockingWrite {
+        writer: Some(writer),
+        buf: Vec::new(),
+        spare: Vec::new(),
+        worker: None,
+        in_flight: None,
+    }
+}
+
+fn worker_gone() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "background I/O thread exited")
+}
+
+fn would_block() -> io::Error {
+    io::Error::new(io::ErrorKind::WouldBlock, "would block")
+}
+
+impl<R> BlockingRead<R>
+    where R: Read + Send + 'static,
+{
+    // Asks the background thread for the next chunk of data, starting it if
+    // needed.
+    fn start_read(&mut self) -> io::Result<()> {
+        if let Some(mut reader) = self.reader.take() {
+            let (tx, rx) = mpsc::channel::<(Vec<u8>, oneshot::Sender<ReadDone>)>();
+            try!(thread::Builder::new()
+                .name("tokio-io-blocking-read".to_string())
+                .spawn(move || {
+                    for (mut buf, done) in rx {
+                        let res = reader.read(&mut buf);
+                        let _ = done.send((buf, res));
+                    }
+                }));
+            self.worker = Some(tx);
+        }
+
+        let mut buf = mem::replace(&mut self.buf, Vec::new());
+        buf.resize(BUF_LEN, 0);
+        self.pos = 0;
+        let (tx, rx) = oneshot::channel();
+        match self.worker.as_ref().unwrap().send((buf, tx)) {
+            Ok(()) => {
+                self.in_flight = Some(rx);
+                Ok(())
+            }
+            Err(_) => Err(worker_gone()),
+        }
+    }
+}
+
+impl<R> Read for BlockingRead<R>
+    where R: Read + Send + 'static,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = {
+            let mut rem = try!(self.fill_buf());
+            try!(rem.read(buf))
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R> BufRead for BlockingRead<R>
+    where R: Read + Send + 'static,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos == self.buf.len() {
+            if self.in_flight.is_none() {
+                try!(self.start_read());
+            }
+            let (mut buf, res) = match self.in_flight.as_mut().unwrap().poll() {
+                Ok(Async::Ready(done)) => done,
+                Ok(Async::NotReady) => return Err(would_block()),
+                Err(_) => return Err(worker_gone()),
+            };
+            self.in_flight = None;
+            let n = match res {
+                Ok(n) => n,
+                Err(e) => {
+                    buf.clear();
+                    self.buf = buf;
+                    return Err(e);
+                }
+            };
+            buf.truncate(n);
+            self.buf = buf;
+        }
+        Ok(&self.buf[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = cmp::min(self.pos + amt, self.buf.len());
+    }
+}
+
+impl<R> AsyncRead for BlockingRead<R>
+    where R: Read + Send + 'static,
+{
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+}
+
+impl<R> fmt::Debug for BlockingRead<R>
+    where R: Read + Send + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BlockingRead")
+            .field("buffered", &(self.buf.len() - self.pos))
+            .field("in_flight", &self.in_flight.is_some())
+            .finish()
+    }
+}
+
+impl<W> BlockingWrite<W>
+    where W: Write + Send + 'static,
+{
+    // Hands the buffered data to the background thread, starting it if
+    // needed. Must only be called when nothing is in flight, except when
+    // dropping.
+    fn start_write(&mut self) -> io::Result<()> {
+        if let Some(mut writer) = self.writer.take() {
+            let (tx, rx) = mpsc::channel::<(Vec<u8>, oneshot::Sender<WriteDone>)>();
+            try!(thread::Builder::new()
+                .name("tokio-io-blocking-write".to_string())
+                .spawn(move || {
+                    for (data, done) in rx {
+                        let res = writer.write_all(&data).and_then(|()| writer.flush());
+                        let _ = done.send((data, res));
+                    }
+                }));
+            self.worker = Some(tx);
+        }
+
+        let data = mem::replace(&mut self.buf, mem::replace(&mut self.spare, Vec::new()));
+        let (tx, rx) = oneshot::channel();
+        match self.worker.as_ref().unwrap().send((data, tx)) {
+            Ok(()) => {
+                self.in_flight = Some(rx);
+                Ok(())
+            }
+            Err(_) => Err(worker_gone()),
+        }
+    }
+
+    // Waits for the data handed to the background thread to be written out.
+    fn poll_in_flight(&mut self) -> Poll<(), io::Error> {
+        let (mut data, res) = match self.in_flight {
+            Some(ref mut rx) => match rx.poll() {
+                Ok(Async::Ready(done)) => done,
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Err(_) => return Err(worker_gone()),
+            },
+            None => return Ok(().into()),
+        };
+        self.in_flight = None;
+        data.clear();
+        self.spare = data;
+        try!(res);
+        Ok(().into())
+    }
+}
+
+impl<W> Write for BlockingWrite<W>
+    where W: Write + Send + 'static,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.buf.len() >= BUF_LEN {
+            if let Async::NotReady = try!(self.poll_in_flight()) {
+                return Err(would_block());
+            }
+            try!(self.start_write());
+        }
+        if self.buf.capacity() == 0 {
+            self.buf.reserve(BUF_LEN);
+        }
+        let n = cmp::min(buf.len(), BUF_LEN - self.buf.len());
+        self.buf.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        loop {
+            if let Async::NotReady = try!(self.poll_in_flight()) {
+                return Err(would_block());
+            }
+            if self.buf.is_empty() {
+                return Ok(());
+            }
+            try!(self.start_write());
+        }
+    }
+}
+
+impl<W> AsyncWrite for BlockingWrite<W>
+    where W: Write + Send + 'static,
+{
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        try_nb!(self.flush());
+        Ok(().into())
+    }
+}
+
+impl<W> Drop for BlockingWrite<W>
+    where W: Write + Send + 'static,
+{
+    fn drop(&mut self) {
+        // The background thread writes out its queue in order, so any data
+        // still in flight is written before the rest of the buffer.
+        if !self.buf.is_empty() {
+            let _ = self.start_write();
+        }
+    }
+}
+
+impl<W> fmt::Debug for BlockingWrite<W>
+    where W: Write + Send + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BlockingWrite")
+            .field("buffered", &self.buf.len())
+            .field("in_flight", &self.in_flight.is_some())
+            .finish()
+    }
+}
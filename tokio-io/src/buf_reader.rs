@@ -0,0 +1,303 @@
+use std::cmp;
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+
+use bytes::{BufMut, BytesMut};
+use futures::{Async, Future, Poll};
+
+use {AsyncRead, AsyncWrite};
+
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// Adds buffering to an `AsyncRead`.
+///
+/// This is the asynchronous counterpart of [`std::io::BufReader`] and
+/// implements `BufRead`, so it can be used with all of the buffered helpers
+/// such as [`read_until`]. Unlike the `std` version it can also be asked to
+/// buffer a minimum amount of data, see [`peek`].
+///
+/// [`std::io::BufReader`]: https://doc.rust-lang.org/std/io/struct.BufReader.html
+/// [`read_until`]: fn.read_until.html
+/// [`peek`]: #method.peek
+#[derive(Debug)]
+pub struct BufReader<R> {
+    inner: R,
+    buf: BytesMut,
+    capacity: usize,
+}
+
+/// A future which resolves to the next bytes of a `BufReader` without
+/// consuming them.
+///
+/// Created by the [`BufReader::peek`] method.
+///
+/// [`BufReader::peek`]: struct.BufReader.html#method.peek
+#[derive(Debug)]
+pub struct Peek<'a, R: 'a> {
+    reader: Option<&'a mut BufReader<R>>,
+    n: usize,
+}
+
+impl<R: AsyncRead> BufReader<R> {
+    /// Creates a new `BufReader` with a default buffer capacity of 8KiB.
+    pub fn new(inner: R) -> BufReader<R> {
+        BufReader::with_capacity(DEFAULT_CAPACITY, inner)
+    }
+
+    /// Creates a new `BufReader` which reads up to `capacity` bytes at a time
+    /// from `inner`.
+    ///
+    /// Every time the buffer runs empty, a single read of at most `capacity`
+    /// bytes is issued to the underlying reader. A small capacity keeps memory
+    /// usage low for protocols exchanging small messages, while a large one
+    /// reduces the number of reads needed for bulk transfers. Reads into a
+    /// caller supplied buffer at least as large as `capacity` bypass the
+    /// internal buffer altogether.
+    pub fn with_capacity(capacity: usize, inner: R) -> BufReader<R> {
+        BufReader {
+            inner: inner,
+            buf: BytesMut::with_capacity(capacity),
+            capacity: capacity,
+        }
+    }
+
+    /// Creates a new `BufReader` which buffers data in `buf` rather than
+    /// allocating a buffer of its own.
+    ///
+    /// This allows buffers to be taken from a pool and recycled afterwards,
+    /// with [`into_parts`] returning the buffer. Any data already in `buf` is
+    /// treated as buffered input and returned before reading from `inner`.
+    /// The capacity of `buf` is used as the capacity of the `BufReader`, or
+    /// the default of 8KiB if `buf` has no capacity.
+    ///
+    /// [`into_parts`]: #method.into_parts
+    pub fn from_parts(inner: R, buf: BytesMut) -> BufReader<R> {
+        let capacity = match buf.capacity() {
+            0 => DEFAULT_CAPACITY,
+            n => n,
+        };
+        BufReader {
+            inner: inner,
+            buf: buf,
+            capacity: capacity,
+        }
+    }
+
+    /// Returns a future which resolves to the next `n` bytes of the reader
+    /// without consuming them.
+    ///
+    /// The future reads from the underlying reader until at least `n` bytes
+    /// are buffered, growing the buffer beyond its capacity if needed. If EOF
+    /// is reached first, fewer than `n` bytes are returned. The peeked bytes
+    /// remain buffered and are returned again by subsequent reads.
+    pub fn peek<'a>(&'a mut self, n: usize) -> Peek<'a, R> {
+        Peek {
+            reader: Some(self),
+            n: n,
+        }
+    }
+
+    /// Attempts to buffer at least `at_least` bytes, returning the buffered
+    /// data without consuming it.
+    ///
+    /// This is the polling counterpart of [`peek`]: it reads from the
+    /// underlying reader until at least `at_least` bytes are buffered,
+    /// growing the buffer beyond its capacity if needed, and returns
+    /// `Async::NotReady` if the reader isn't ready yet. If EOF is reached
+    /// first, the returned slice is shorter than `at_least`. Unlike
+    /// `fill_buf`, the returned slice holds all buffered data, which may be
+    /// more than `at_least` bytes.
+    ///
+    /// This is only available on `BufReader` since `BufRead::fill_buf` doesn't
+    /// read more data until its buffer has been consumed entirely.
+    ///
+    /// [`peek`]: #method.peek
+    pub fn poll_fill_buf_at_least(&mut self, at_least: usize) -> Poll<&[u8], io::Error> {
+        try_ready!(self.poll_fill_to(at_least));
+        Ok(Async::Ready(&self.buf))
+    }
+
+    // Reads from the underlying reader until at least `n` bytes are buffered
+    // or EOF is reached.
+    fn poll_fill_to(&mut self, n: usize) -> Poll<(), io::Error> {
+        while self.buf.len() < n {
+            let additional = cmp::max(n, self.capacity) - self.buf.len();
+            self.buf.reserve(additional);
+
+            // `reserve` may leave more room than asked for, don't read more
+            // than `additional` bytes so the buffer stays within its capacity.
+            let read = unsafe {
+                let read = {
+                    let dst = &mut self.buf.bytes_mut()[..additional];
+                    self.inner.prepare_uninitialized_buffer(dst);
+                    try_ready!(self.inner.poll_read(dst))
+                };
+                self.buf.advance_mut(read);
+                read
+            };
+            if read == 0 {
+                break;
+            }
+        }
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<R> BufReader<R> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Note that reading directly from the underlying reader will skip over
+    /// any data which is still buffered.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the number of bytes read from the underlying reader at a time.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the data which has been buffered but not yet consumed.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Consumes the `BufReader`, returning the underlying reader.
+    ///
+    /// Note that any buffered data is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Consumes the `BufReader`, returning the underlying reader and the data
+    /// which has been buffered but not yet consumed.
+    pub fn into_parts(self) -> (R, BytesMut) {
+        (self.inner, self.buf)
+    }
+}
+
+fn would_block() -> io::Error {
+    io::Error::new(io::ErrorKind::WouldBlock, "would block")
+}
+
+impl<R: AsyncRead> Read for BufReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Bypass our own buffer entirely for large reads when it's empty.
+        if self.buf.is_empty() && buf.len() >= self.capacity {
+            return self.inner.read(buf);
+        }
+        let n = {
+            let mut rem = self.fill_buf()?;
+            rem.read(buf)?
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: AsyncRead> BufRead for BufReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.buf.is_empty() {
+            if let Async::NotReady = self.poll_fill_to(1)? {
+                return Err(would_block());
+            }
+        }
+        Ok(&self.buf)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = cmp::min(amt, self.buf.len());
+        let _ = self.buf.split_to(amt);
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for BufReader<R> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<R: AsyncRead + Seek> BufReader<R> {
+    /// Seeks relative to the current position without discarding the buffer
+    /// if possible.
+    ///
+    /// If the new position lies within the buffered data, the buffer is simply
+    /// advanced and the underlying reader isn't touched. Otherwise this
+    /// behaves like `seek(SeekFrom::Current(offset))`. Unlike `seek` this
+    /// doesn't return the new position, since determining it would require a
+    /// seek on the underlying reader.
+    pub fn seek_relative(&mut self, offset: i64) -> io::Result<()> {
+        if offset >= 0 && offset as u64 <= self.buf.len() as u64 {
+            self.consume(offset as usize);
+            return Ok(());
+        }
+        self.seek(SeekFrom::Current(offset)).map(|_| ())
+    }
+}
+
+impl<R: AsyncRead + Seek> Seek for BufReader<R> {
+    /// Seeks to an offset, in bytes, in the underlying reader.
+    ///
+    /// The position used for `SeekFrom::Current(_)` is the position the
+    /// underlying reader would be at if the `BufReader` had no internal
+    /// buffer. Seeking always discards the internal buffer, even if the seek
+    /// position would otherwise fall within it; use [`seek_relative`] to
+    /// avoid that.
+    ///
+    /// [`seek_relative`]: #method.seek_relative
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let result = match pos {
+            SeekFrom::Current(n) => {
+                let remainder = self.buf.len() as i64;
+                // If the offset would overflow, seek back to where the
+                // underlying reader actually is first and then apply `n`.
+                match n.checked_sub(remainder) {
+                    Some(offset) => try!(self.inner.seek(SeekFrom::Current(offset))),
+                    None => {
+                        try!(self.inner.seek(SeekFrom::Current(-remainder)));
+                        self.buf.clear();
+                        try!(self.inner.seek(SeekFrom::Current(n)))
+                    }
+                }
+            }
+            pos => try!(self.inner.seek(pos)),
+        };
+        self.buf.clear();
+        Ok(result)
+    }
+}
+
+impl<R: Write> Write for BufReader<R> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<R: AsyncWrite> AsyncWrite for BufReader<R> {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.inner.shutdown()
+    }
+}
+
+impl<'a, R: AsyncRead> Future for Peek<'a, R> {
+    type Item = &'a [u8];
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<&'a [u8], io::Error> {
+        {
+            let reader = self.reader.as_mut().expect("poll Peek after it's done");
+            try_ready!(reader.poll_fill_to(self.n));
+        }
+        let reader = self.reader.take().unwrap();
+        let n = cmp::min(self.n, reader.buf.len());
+        Ok(Async::Ready(&reader.buf[..n]))
+    }
+}
@@ -0,0 +1,133 @@
+use std::io::{self, BufRead, Read, Write};
+
+use bytes::BytesMut;
+use futures::Poll;
+
+use {AsyncRead, AsyncWrite};
+use buf_reader::BufReader;
+use buf_writer::BufWriter;
+
+/// Wraps a type that is both `AsyncRead` and `AsyncWrite` and buffers its
+/// input and output.
+///
+/// This is equivalent to wrapping the stream in a [`BufWriter`] and the result
+/// in a [`BufReader`], but keeps a single type that can be read from and
+/// written to. The two buffers are independent of each other.
+///
+/// [`BufReader`]: struct.BufReader.html
+/// [`BufWriter`]: struct.BufWriter.html
+#[derive(Debug)]
+pub struct BufStream<S> {
+    inner: BufReader<BufWriter<S>>,
+}
+
+impl<S: AsyncRead + AsyncWrite> BufStream<S> {
+    /// Creates a new `BufStream` with default buffer capacities of 8KiB for
+    /// both reading and writing.
+    pub fn new(stream: S) -> BufStream<S> {
+        BufStream {
+            inner: BufReader::new(BufWriter::new(stream)),
+        }
+    }
+
+    /// Creates a new `BufStream` with the specified read and write buffer
+    /// capacities.
+    ///
+    /// See [`BufReader::with_capacity`] and [`BufWriter::with_capacity`] for
+    /// how the capacities affect reads and writes.
+    ///
+    /// [`BufReader::with_capacity`]: struct.BufReader.html#method.with_capacity
+    /// [`BufWriter::with_capacity`]: struct.BufWriter.html#method.with_capacity
+    pub fn with_capacity(read_capacity: usize,
+                         write_capacity: usize,
+                         stream: S) -> BufStream<S> {
+        BufStream {
+            inner: BufReader::with_capacity(
+                read_capacity,
+                BufWriter::with_capacity(write_capacity, stream)),
+        }
+    }
+}
+
+impl<S> BufStream<S> {
+    /// Returns a reference to the underlying stream.
+    pub fn get_ref(&self) -> &S {
+        self.inner.get_ref().get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying stream.
+    ///
+    /// Note that reading from or writing to the underlying stream directly
+    /// bypasses any data which is still buffered.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.inner.get_mut().get_mut()
+    }
+
+    /// Returns the data which has been read but not yet consumed.
+    ///
+    /// Its length is the number of bytes which can be read without reading
+    /// from the underlying stream again.
+    pub fn read_buffer(&self) -> &[u8] {
+        self.inner.buffer()
+    }
+
+    /// Returns the data which has been buffered for writing but not yet
+    /// written out.
+    pub fn write_buffer(&self) -> &[u8] {
+        self.inner.get_ref().buffer()
+    }
+
+    /// Consumes the `BufStream`, returning the underlying stream.
+    ///
+    /// Note that any buffered data is lost.
+    pub fn into_inner(self) -> S {
+        self.inner.into_inner().into_inner()
+    }
+
+    /// Consumes the `BufStream`, returning the underlying stream, the data
+    /// which has been read but not yet consumed, and the data which has been
+    /// buffered for writing but not yet written out, in that order.
+    pub fn into_parts(self) -> (S, BytesMut, BytesMut) {
+        let (writer, read_buf) = self.inner.into_parts();
+        let (stream, write_buf) = writer.into_parts();
+        (stream, read_buf, write_buf)
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite> Read for BufStream<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite> BufRead for BufStream<S> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite> AsyncRead for BufStream<S> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite> Write for BufStream<S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite> AsyncWrite for BufStream<S> {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.inner.shutdown()
+    }
+}
@@ -0,0 +1,235 @@
+use std::cmp;
+use std::io::{self, Read, Write};
+
+use bytes::{BufMut, BytesMut};
+use futures::{Async, Future, Poll};
+
+use {AsyncBufWrite, AsyncRead, AsyncWrite};
+
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// Wraps an `AsyncWrite` and buffers its output.
+///
+/// This is the asynchronous counterpart of [`std::io::BufWriter`]. Small
+/// writes are collected in an internal `BytesMut` and only written out to the
+/// underlying I/O object once the buffer is full, or when it is explicitly
+/// flushed. The buffer is exposed through [`get_buf_mut`] and
+/// [`AsyncBufWrite`] so that data can be encoded directly into it without an
+/// intermediate copy.
+///
+/// Note that any data left in the buffer when the `BufWriter` is dropped is
+/// lost. Use [`flush_buf`] or `poll_flush` to make sure it reaches the
+/// underlying writer.
+///
+/// [`std::io::BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
+/// [`get_buf_mut`]: #method.get_buf_mut
+/// [`AsyncBufWrite`]: ../trait.AsyncBufWrite.html
+/// [`flush_buf`]: #method.flush_buf
+#[derive(Debug)]
+pub struct BufWriter<W> {
+    inner: W,
+    buf: BytesMut,
+    capacity: usize,
+    // The number of zeroed bytes past the end of `buf` lent out by the last
+    // call to `poll_write_buf`, which `advance` may commit.
+    lent: usize,
+}
+
+/// A future which writes out all data buffered in a `BufWriter`.
+///
+/// Resolves to the `BufWriter` once its buffer is empty.
+///
+/// Created by the [`BufWriter::flush_buf`] method.
+///
+/// [`BufWriter::flush_buf`]: struct.BufWriter.html#method.flush_buf
+#[derive(Debug)]
+pub struct FlushBuf<W> {
+    writer: Option<BufWriter<W>>,
+}
+
+impl<W: AsyncWrite> BufWriter<W> {
+    /// Creates a new `BufWriter` with a default buffer capacity of 8KiB.
+    pub fn new(inner: W) -> BufWriter<W> {
+        BufWriter::with_capacity(DEFAULT_CAPACITY, inner)
+    }
+
+    /// Creates a new `BufWriter` which buffers up to `capacity` bytes before
+    /// writing to `inner`.
+    ///
+    /// Buffered data is written out once a write would not fit in the
+    /// remaining space, so a larger capacity means fewer, larger writes to the
+    /// underlying writer. Writes of at least `capacity` bytes are passed
+    /// straight through to the underlying writer.
+    pub fn with_capacity(capacity: usize, inner: W) -> BufWriter<W> {
+        BufWriter {
+            inner: inner,
+            buf: BytesMut::with_capacity(capacity),
+            capacity: capacity,
+            lent: 0,
+        }
+    }
+
+    /// Attempts to write all buffered data to the underlying writer.
+    ///
+    /// Unlike `poll_flush` this does not flush the underlying writer itself.
+    pub fn poll_flush_buf(&mut self) -> Poll<(), io::Error> {
+        self.lent = 0;
+        while !self.buf.is_empty() {
+            let n = try_ready!(self.inner.poll_write(&self.buf));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to write buffered data"));
+            }
+            let _ = self.buf.split_to(n);
+        }
+        Ok(Async::Ready(()))
+    }
+
+    /// Returns a future which writes all buffered data to the underlying
+    /// writer and then yields the `BufWriter` back.
+    pub fn flush_buf(self) -> FlushBuf<W> {
+        FlushBuf {
+            writer: Some(self),
+        }
+    }
+}
+
+impl<W> BufWriter<W> {
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Note that writing directly to the underlying writer will bypass any
+    /// data which is still buffered.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the number of bytes which are buffered before writing to the
+    /// underlying writer.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the data which has been buffered but not yet written out.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Returns a mutable reference to the internal buffer.
+    ///
+    /// Any bytes appended to the buffer are written to the underlying writer
+    /// on the next flush, after the data which is already buffered.
+    pub fn get_buf_mut(&mut self) -> &mut BytesMut {
+        self.lent = 0;
+        &mut self.buf
+    }
+
+    /// Consumes the `BufWriter`, returning the underlying writer.
+    ///
+    /// Note that any buffered data is lost. It's recommended to only call this
+    /// once the buffer has been flushed.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Consumes the `BufWriter`, returning the underlying writer and the data
+    /// which has been buffered but not yet written out.
+    pub fn into_parts(self) -> (W, BytesMut) {
+        (self.inner, self.buf)
+    }
+}
+
+fn would_block() -> io::Error {
+    io::Error::new(io::ErrorKind::WouldBlock, "would block")
+}
+
+impl<W: AsyncWrite> Write for BufWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.lent = 0;
+        if self.buf.len() + buf.len() > self.capacity {
+            if let Async::NotReady = self.poll_flush_buf()? {
+                return Err(would_block());
+            }
+        }
+        if buf.len() >= self.capacity {
+            self.inner.write(buf)
+        } else {
+            self.buf.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if let Async::NotReady = self.poll_flush_buf()? {
+            return Err(would_block());
+        }
+        self.inner.flush()
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for BufWriter<W> {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        try_ready!(self.poll_flush_buf());
+        try_ready!(self.inner.poll_flush());
+        self.inner.shutdown()
+    }
+}
+
+impl<W: AsyncWrite> AsyncBufWrite for BufWriter<W> {
+    fn poll_write_buf(&mut self) -> Poll<&mut [u8], io::Error> {
+        // Always lend out at least one byte, even without any capacity.
+        let capacity = cmp::max(self.capacity, 1);
+        if self.buf.len() >= capacity {
+            try_ready!(self.poll_flush_buf());
+        }
+        // Zero the spare capacity through `resize` before lending it out,
+        // so that no reference to uninitialized memory is ever created.
+        let len = self.buf.len();
+        let spare = capacity - len;
+        self.buf.resize(capacity, 0);
+        self.lent = spare;
+        unsafe {
+            self.buf.set_len(len);
+            Ok(Async::Ready(&mut self.buf.bytes_mut()[..spare]))
+        }
+    }
+
+    fn advance(&mut self, n: usize) {
+        // Only bytes zeroed by `poll_write_buf` may become part of the
+        // buffer, anything else would expose uninitialized memory.
+        assert!(n <= self.lent, "advanced past the end of the lent write buffer");
+        self.lent = 0;
+        unsafe {
+            self.buf.advance_mut(n);
+        }
+    }
+}
+
+impl<W: Read> Read for BufWriter<W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<W: AsyncRead> AsyncRead for BufWriter<W> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<W: AsyncWrite> Future for FlushBuf<W> {
+    type Item = BufWriter<W>;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<BufWriter<W>, io::Error> {
+        {
+            let writer = self.writer.as_mut().expect("poll FlushBuf after it's done");
+            try_ready!(writer.poll_flush_buf());
+        }
+        Ok(Async::Ready(self.writer.take().unwrap()))
+    }
+}
@@ -0,0 +1,116 @@
+use std::{fmt, io};
+
+use bytes::BytesMut;
+use codec::Decoder;
+
+/// A `Decoder` which decodes a frame with one decoder, and then the frame
+/// following it with a decoder created from the first frame.
+///
+/// Created by the [`Decoder::and_then`] method.
+///
+/// [`Decoder::and_then`]: trait.Decoder.html#method.and_then
+pub struct AndThenDecoder<A: Decoder, B, F> {
+    first: A,
+    f: F,
+    // The frame decoded by `first` and the decoder for the frame following it
+    second: Option<(A::Item, B)>,
+}
+
+pub fn new<A: Decoder, B, F>(first: A, f: F) -> AndThenDecoder<A, B, F> {
+    AndThenDecoder {
+        first: first,
+        f: f,
+        second: None,
+    }
+}
+
+impl<A: Decoder, B, F> AndThenDecoder<A, B, F> {
+    /// Returns a reference to the first decoder.
+    pub fn get_ref(&self) -> &A {
+        &self.first
+    }
+
+    /// Returns a mutable reference to the first decoder.
+    pub fn get_mut(&mut self) -> &mut A {
+        &mut self.first
+    }
+
+    /// Consumes the `AndThenDecoder`, returning the first decoder.
+    ///
+    /// Note that a frame which was decoded by the first decoder but is still
+    /// waiting for the frame following it is lost.
+    pub fn into_inner(self) -> A {
+        self.first
+    }
+}
+
+impl<A, B, F> Decoder for AndThenDecoder<A, B, F>
+    where A: Decoder,
+          B: Decoder,
+          F: FnMut(&A::Item) -> B,
+          A::Error: From<B::Error>,
+{
+    type Item = (A::Item, B::Item);
+    type Error = A::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, A::Error> {
+        if self.second.is_none() {
+            match try!(self.first.decode(buf)) {
+                Some(item) => {
+                    let second = (self.f)(&item);
+                    self.second = Some((item, second));
+                }
+                None => return Ok(None),
+            }
+        }
+
+        let frame = match self.second {
+            Some((_, ref mut second)) => try!(second.decode(buf)),
+            None => unreachable!(),
+        };
+        Ok(frame.map(|frame| (self.second.take().unwrap().0, frame)))
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, A::Error> {
+        if self.second.is_none() {
+            match try!(self.first.decode_eof(buf)) {
+                Some(item) => {
+                    let second = (self.f)(&item);
+                    self.second = Some((item, second));
+                }
+                None => return Ok(None),
+            }
+        }
+
+        let frame = match self.second {
+            Some((_, ref mut second)) => try!(second.decode_eof(buf)),
+            None => unreachable!(),
+        };
+        match frame {
+            Some(frame) => Ok(Some((self.second.take().unwrap().0, frame))),
+            None => {
+                Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                   "stream ended before the second frame").into())
+            }
+        }
+    }
+
+    fn max_buf_size(&self) -> usize {
+        match self.second {
+            Some((_, ref second)) => second.max_buf_size(),
+            None => self.first.max_buf_size(),
+        }
+    }
+}
+
+impl<A, B, F> fmt::Debug for AndThenDecoder<A, B, F>
+    where A: Decoder + fmt::Debug,
+          B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AndThenDecoder")
+            .field("first", &self.first)
+            .field("second", &self.second.as_ref().map(|&(_, ref second)| second))
+            .finish()
+    }
+}
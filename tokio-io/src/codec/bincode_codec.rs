@@ -0,0 +1,302 @@
+use std::{error, fmt, io};
+use std::marker::PhantomData;
+
+use bincode::{self, DefaultOptions, Options};
+use bytes::{Bytes, BytesMut};
+use codec::{Decoder, Encoder};
+use length_delimited::{Builder, LengthDelimitedCodec};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// A `Codec` implementation that frames values of type `T` serialized with
+/// `bincode`.
+///
+/// Every value is serialized into a frame of its own, which is prefixed by
+/// its length like the frames of [`LengthDelimitedCodec`]. By default the
+/// length is a big-endian `u32` and values are encoded like
+/// `bincode::serialize` does; use [`BincodeCodecConfig`] to change that.
+///
+/// This codec is only available when the `bincode-codec` feature is enabled.
+///
+/// [`LengthDelimitedCodec`]: length_delimited/struct.LengthDelimitedCodec.html
+/// [`BincodeCodecConfig`]: struct.BincodeCodecConfig.html
+pub struct BincodeCodec<T> {
+    frames: LengthDelimitedCodec,
+    options: BincodeOptions,
+    _marker: PhantomData<fn(T) -> T>,
+}
+
+/// Configures and creates a [`BincodeCodec`].
+///
+/// [`BincodeCodec`]: struct.BincodeCodec.html
+pub struct BincodeCodecConfig<T> {
+    frames: Builder,
+    options: BincodeOptions,
+    _marker: PhantomData<fn(T) -> T>,
+}
+
+/// An error which occurred while encoding or decoding values with a
+/// [`BincodeCodec`].
+///
+/// [`BincodeCodec`]: struct.BincodeCodec.html
+#[derive(Debug)]
+pub enum BincodeCodecError {
+    /// An I/O error, including frames exceeding the max frame length.
+    Io(io::Error),
+    /// A value couldn't be serialized, or a frame couldn't be deserialized.
+    Bincode(bincode::Error),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct BincodeOptions {
+    big_endian: bool,
+    varint: bool,
+}
+
+// `bincode::Options` are configured through types, so every combination of
+// options has to be spelled out.
+macro_rules! with_options {
+    ($options:expr, $opts:ident => $e:expr) => {
+        match ($options.big_endian, $options.varint) {
+            (false, false) => {
+                let $opts = DefaultOptions::new().with_little_endian().with_fixint_encoding();
+                $e
+            }
+            (false, true) => {
+                let $opts = DefaultOptions::new().with_little_endian().with_varint_encoding();
+                $e
+            }
+            (true, false) => {
+                let $opts = DefaultOptions::new().with_big_endian().with_fixint_encoding();
+                $e
+            }
+            (true, true) => {
+                let $opts = DefaultOptions::new().with_big_endian().with_varint_encoding();
+                $e
+            }
+        }
+    }
+}
+
+impl BincodeOptions {
+    fn serialize<T: Serialize>(&self, value: &T) -> bincode::Result<Vec<u8>> {
+        with_options!(self, opts => opts.serialize(value))
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> bincode::Result<T> {
+        with_options!(self, opts => opts.deserialize(bytes))
+    }
+}
+
+impl<T> BincodeCodec<T>
+    where T: Serialize + DeserializeOwned,
+{
+    /// Returns a `BincodeCodec` with the default configuration.
+    pub fn new() -> BincodeCodec<T> {
+        BincodeCodecConfig::new().new_codec()
+    }
+
+    /// Returns a [`BincodeCodecConfig`] with the default configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio_io::codec::BincodeCodec;
+    ///
+    /// let codec = BincodeCodec::<Vec<u32>>::config()
+    ///     .length_field_length(2)
+    ///     .varint_encoding()
+    ///     .new_codec();
+    /// # let _ = codec;
+    /// ```
+    ///
+    /// [`BincodeCodecConfig`]: struct.BincodeCodecConfig.html
+    pub fn config() -> BincodeCodecConfig<T> {
+        BincodeCodecConfig::new()
+    }
+
+    /// Returns the largest encoded value this codec will accept from the
+    /// wire and write to the wire.
+    pub fn max_frame_length(&self) -> usize {
+        self.frames.max_frame_length()
+    }
+}
+
+impl<T> BincodeCodecConfig<T>
+    where T: Serialize + DeserializeOwned,
+{
+    /// Returns a `BincodeCodecConfig` with the default configuration.
+    ///
+    /// This uses a big-endian `u32` length field, a max frame length of
+    /// 8MiB, and encodes values with fixed size little-endian integers.
+    pub fn new() -> BincodeCodecConfig<T> {
+        BincodeCodecConfig {
+            frames: Builder::new(),
+            options: BincodeOptions {
+                big_endian: false,
+                varint: false,
+            },
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the number of bytes of the length field prefixing every frame.
+    ///
+    /// The default value is `4`. The max value is `8`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `val` is `0` or larger than `8`.
+    pub fn length_field_length(&mut self, val: usize) -> &mut Self {
+        self.frames.length_field_length(val);
+        self
+    }
+
+    /// Sets the max length of an encoded value.
+    ///
+    /// Larger frames result in an `io::Error` of kind `InvalidData` when
+    /// decoding and `InvalidInput` when encoding. The default is 8MiB.
+    pub fn max_frame_length(&mut self, val: usize) -> &mut Self {
+        self.frames.max_frame_length(val);
+        self
+    }
+
+    /// Encodes the integers within values as big-endian.
+    ///
+    /// This doesn't affect the length field, which is always big-endian.
+    pub fn big_endian(&mut self) -> &mut Self {
+        self.options.big_endian = true;
+        self
+    }
+
+    /// Encodes the integers within values as little-endian.
+    ///
+    /// This is the default.
+    pub fn little_endian(&mut self) -> &mut Self {
+        self.options.big_endian = false;
+        self
+    }
+
+    /// Encodes the integers within values with a variable length, using
+    /// fewer bytes for small values.
+    pub fn varint_encoding(&mut self) -> &mut Self {
+        self.options.varint = true;
+        self
+    }
+
+    /// Encodes the integers within values with their full size.
+    ///
+    /// This is the default.
+    pub fn fixint_encoding(&mut self) -> &mut Self {
+        self.options.varint = false;
+        self
+    }
+
+    /// Creates a `BincodeCodec` with the current configuration.
+    pub fn new_codec(&self) -> BincodeCodec<T> {
+        BincodeCodec {
+            frames: self.frames.new_codec(),
+            options: self.options,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Decoder for BincodeCodec<T>
+    where T: Serialize + DeserializeOwned,
+{
+    type Item = T;
+    type Error = BincodeCodecError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<T>, BincodeCodecError> {
+        match try!(self.frames.decode(buf)) {
+            Some(frame) => Ok(Some(try!(self.options.deserialize(&frame)))),
+            None => Ok(None),
+        }
+    }
+
+    fn max_buf_size(&self) -> usize {
+        self.frames.max_buf_size()
+    }
+}
+
+impl<T> Encoder for BincodeCodec<T>
+    where T: Serialize + DeserializeOwned,
+{
+    type Item = T;
+    type Error = BincodeCodecError;
+
+    fn encode(&mut self, item: T, buf: &mut BytesMut) -> Result<(), BincodeCodecError> {
+        let data = try!(self.options.serialize(&item));
+        try!(self.frames.encode(Bytes::from(data), buf));
+        Ok(())
+    }
+}
+
+impl<T> Clone for BincodeCodec<T> {
+    fn clone(&self) -> BincodeCodec<T> {
+        BincodeCodec {
+            frames: self.frames.clone(),
+            options: self.options,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for BincodeCodec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BincodeCodec")
+            .field("frames", &self.frames)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl<T> Clone for BincodeCodecConfig<T> {
+    fn clone(&self) -> BincodeCodecConfig<T> {
+        BincodeCodecConfig {
+            frames: self.frames,
+            options: self.options,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for BincodeCodecConfig<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BincodeCodecConfig")
+            .field("frames", &self.frames)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl From<io::Error> for BincodeCodecError {
+    fn from(e: io::Error) -> BincodeCodecError {
+        BincodeCodecError::Io(e)
+    }
+}
+
+impl From<bincode::Error> for BincodeCodecError {
+    fn from(e: bincode::Error) -> BincodeCodecError {
+        BincodeCodecError::Bincode(e)
+    }
+}
+
+impl fmt::Display for BincodeCodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BincodeCodecError::Io(ref e) => write!(f, "I/O error: {}", e),
+            BincodeCodecError::Bincode(ref e) => write!(f, "bincode error: {}", e),
+        }
+    }
+}
+
+impl error::Error for BincodeCodecError {
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            BincodeCodecError::Io(ref e) => Some(e),
+            BincodeCodecError::Bincode(ref e) => Some(e),
+        }
+    }
+}
@@ -1,14 +1,99 @@
 use bytes::{Bytes, BufMut, BytesMut};
 use codec::{Encoder, Decoder};
-use std::io;
+use codec::decoder::DEFAULT_MAX_BUF_SIZE;
+use std::{fmt, io, usize};
+use std::error::Error as StdError;
 
 /// A simple `Codec` implementation that just ships bytes around.
+///
+/// Frames are encoded from `Bytes`. Since `Encoder` has a single item type,
+/// string slices and other byte containers have to be converted first, which
+/// `Bytes` supports through `From`:
+///
+/// ```
+/// # extern crate tokio_io;
+/// # extern crate bytes;
+/// # extern crate futures;
+/// use tokio_io::AsyncWrite;
+/// use tokio_io::codec::{BytesCodec, FramedWrite};
+/// use bytes::Bytes;
+/// use futures::{Future, Sink};
+///
+/// fn send_hello<T: AsyncWrite>(io: T) {
+///     let framed = FramedWrite::new(io, BytesCodec::new());
+///     framed.send(Bytes::from("hello")).wait().unwrap();
+/// }
+/// # pub fn main() {}
+/// ```
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub struct BytesCodec(());
+pub struct BytesCodec {
+    max_buf_size: usize,
+    max_size: usize,
+}
 
 impl BytesCodec {
     /// Creates a new `BytesCodec` for shipping around raw bytes.
-    pub fn new() -> BytesCodec { BytesCodec(())  }
+    pub fn new() -> BytesCodec {
+        BytesCodec {
+            max_buf_size: DEFAULT_MAX_BUF_SIZE,
+            max_size: usize::MAX,
+        }
+    }
+
+    /// Sets the value returned by `Decoder::max_buf_size`.
+    ///
+    /// Since every call to `decode` takes all buffered bytes, this limit is
+    /// only reached if a single read returns more than `max_buf_size` bytes.
+    pub fn with_max_buf_size(self, max_buf_size: usize) -> BytesCodec {
+        BytesCodec {
+            max_buf_size: max_buf_size,
+            ..self
+        }
+    }
+
+    /// Sets the maximum size of a decoded frame.
+    ///
+    /// When more than `max_size` bytes are buffered, `decode` discards them
+    /// and returns an `io::Error` of kind `InvalidData` wrapping a
+    /// [`BytesCodecError::TooLarge`] instead of yielding a frame. This is
+    /// mostly useful for datagram transports, where every datagram is decoded
+    /// on its own and a single oversized one shouldn't be accepted.
+    ///
+    /// [`BytesCodecError::TooLarge`]: enum.BytesCodecError.html#variant.TooLarge
+    pub fn with_max_size(self, max_size: usize) -> BytesCodec {
+        BytesCodec {
+            max_size: max_size,
+            ..self
+        }
+    }
+
+    /// Returns the maximum size of a decoded frame.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+}
+
+/// The reason a [`BytesCodec`] refused to decode a frame.
+///
+/// This is returned as the inner error of an `io::Error` of kind
+/// `InvalidData`, and can be retrieved using `io::Error::get_ref`.
+///
+/// [`BytesCodec`]: struct.BytesCodec.html
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BytesCodecError {
+    /// The buffered data, of the given length, exceeded the maximum frame
+    /// size.
+    TooLarge(usize),
+}
+
+fn decode_len(max_size: usize, buf: &mut BytesMut) -> Result<usize, io::Error> {
+    let len = buf.len();
+    if len > max_size {
+        buf.clear();
+        return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                  BytesCodecError::TooLarge(len)));
+    }
+    Ok(len)
 }
 
 impl Decoder for BytesCodec {
@@ -16,13 +101,17 @@ impl Decoder for BytesCodec {
     type Error = io::Error;
 
     fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<BytesMut>, io::Error> {
-        if buf.len() > 0 {
-            let len = buf.len();
+        let len = try!(decode_len(self.max_size, buf));
+        if len > 0 {
             Ok(Some(buf.split_to(len)))
         } else {
             Ok(None)
         }
     }
+
+    fn max_buf_size(&self) -> usize {
+        self.max_buf_size
+    }
 }
 
 impl Encoder for BytesCodec {
@@ -35,3 +124,95 @@ impl Encoder for BytesCodec {
         Ok(())
     }
 }
+
+/// A `Codec` implementation like [`BytesCodec`] which decodes into frozen
+/// `Bytes` instead of `BytesMut`.
+///
+/// Each frame is split off the read buffer and frozen without copying, so it
+/// can be cheaply cloned and shared, e.g. when forwarding the same data to
+/// several consumers.
+///
+/// [`BytesCodec`]: struct.BytesCodec.html
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct FrozenBytesCodec {
+    max_buf_size: usize,
+    max_size: usize,
+}
+
+impl FrozenBytesCodec {
+    /// Creates a new `FrozenBytesCodec` for shipping around raw bytes.
+    pub fn new() -> FrozenBytesCodec {
+        FrozenBytesCodec {
+            max_buf_size: DEFAULT_MAX_BUF_SIZE,
+            max_size: usize::MAX,
+        }
+    }
+
+    /// Sets the value returned by `Decoder::max_buf_size`.
+    ///
+    /// See [`BytesCodec::with_max_buf_size`] for details.
+    ///
+    /// [`BytesCodec::with_max_buf_size`]: struct.BytesCodec.html#method.with_max_buf_size
+    pub fn with_max_buf_size(self, max_buf_size: usize) -> FrozenBytesCodec {
+        FrozenBytesCodec {
+            max_buf_size: max_buf_size,
+            ..self
+        }
+    }
+
+    /// Sets the maximum size of a decoded frame.
+    ///
+    /// See [`BytesCodec::with_max_size`] for details.
+    ///
+    /// [`BytesCodec::with_max_size`]: struct.BytesCodec.html#method.with_max_size
+    pub fn with_max_size(self, max_size: usize) -> FrozenBytesCodec {
+        FrozenBytesCodec {
+            max_size: max_size,
+            ..self
+        }
+    }
+
+    /// Returns the maximum size of a decoded frame.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+}
+
+impl Decoder for FrozenBytesCodec {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        let len = try!(decode_len(self.max_size, buf));
+        if len > 0 {
+            Ok(Some(buf.split_to(len).freeze()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn max_buf_size(&self) -> usize {
+        self.max_buf_size
+    }
+}
+
+impl Encoder for FrozenBytesCodec {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn encode(&mut self, data: Bytes, buf: &mut BytesMut) -> Result<(), io::Error> {
+        BytesCodec::new().encode(data, buf)
+    }
+}
+
+impl fmt::Display for BytesCodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BytesCodecError::TooLarge(len) => {
+                write!(f, "frame exceeds the maximum size ({} bytes)", len)
+            }
+        }
+    }
+}
+
+impl StdError for BytesCodecError {}
@@ -0,0 +1,169 @@
+use std::{error, fmt, io};
+
+use bytes::{BufMut, BytesMut};
+use codec::{Decoder, Encoder};
+use csv;
+
+/// A `Codec` implementation that frames records of comma separated values.
+///
+/// Each record is decoded into a `Vec<String>` of its fields, following RFC
+/// 4180: fields may be quoted, in which case they can contain commas, line
+/// breaks and quotes escaped as `""`. Records end at a `\n` or `\r\n` outside
+/// of quotes; empty lines are skipped. When encoding, fields are quoted where
+/// necessary and every record is terminated by `\r\n`.
+///
+/// The fields are parsed by the `csv` crate, so this codec is only available
+/// when the `csv` feature is enabled.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct CsvCodec {
+    // Stored index of the next index to examine for the end of a record.
+    next_index: usize,
+
+    // Whether `next_index` is inside a quoted field.
+    in_quotes: bool,
+
+    // Whether a quote at `next_index` starts or continues a quoted section,
+    // which is only the case at the start of a field or right after the
+    // closing quote of one. Elsewhere, quotes are taken literally.
+    quote_allowed: bool,
+}
+
+/// An error which occurred while encoding or decoding records with a
+/// [`CsvCodec`].
+///
+/// [`CsvCodec`]: struct.CsvCodec.html
+#[derive(Debug)]
+pub enum CsvCodecError {
+    /// An I/O error.
+    Io(io::Error),
+    /// A record couldn't be parsed or written.
+    ParseError(csv::Error),
+}
+
+impl CsvCodec {
+    /// Returns a `CsvCodec` for decoding and encoding CSV records.
+    pub fn new() -> CsvCodec {
+        CsvCodec {
+            next_index: 0,
+            in_quotes: false,
+            quote_allowed: true,
+        }
+    }
+
+    // Returns the length of the next record including its line break, if
+    // it's complete.
+    fn find_record_end(&mut self, buf: &BytesMut) -> Option<usize> {
+        for (i, &b) in buf[self.next_index..].iter().enumerate() {
+            if self.in_quotes {
+                // An escaped quote closes the section and opens it again.
+                if b == b'"' {
+                    self.in_quotes = false;
+                    self.quote_allowed = true;
+                }
+                continue;
+            }
+            match b {
+                b'"' if self.quote_allowed => self.in_quotes = true,
+                b',' => self.quote_allowed = true,
+                b'\n' => {
+                    let len = self.next_index + i + 1;
+                    self.next_index = 0;
+                    self.quote_allowed = true;
+                    return Some(len);
+                }
+                _ => self.quote_allowed = false,
+            }
+        }
+        self.next_index = buf.len();
+        None
+    }
+}
+
+// Parses a single record, returning `None` for lines that are blank.
+fn parse(record: &[u8]) -> Result<Option<Vec<String>>, CsvCodecError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(record);
+    let mut fields = csv::StringRecord::new();
+    if !try!(reader.read_record(&mut fields)) {
+        return Ok(None);
+    }
+    Ok(Some(fields.iter().map(|field| field.to_string()).collect()))
+}
+
+impl Decoder for CsvCodec {
+    type Item = Vec<String>;
+    type Error = CsvCodecError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Vec<String>>, CsvCodecError> {
+        while let Some(len) = self.find_record_end(buf) {
+            let record = buf.split_to(len);
+            if let Some(fields) = try!(parse(&record)) {
+                return Ok(Some(fields));
+            }
+        }
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Vec<String>>, CsvCodecError> {
+        if let Some(fields) = try!(self.decode(buf)) {
+            return Ok(Some(fields));
+        }
+        // The last record may not be terminated by a line break.
+        let record = buf.take();
+        self.next_index = 0;
+        self.in_quotes = false;
+        self.quote_allowed = true;
+        parse(&record)
+    }
+}
+
+impl Encoder for CsvCodec {
+    type Item = Vec<String>;
+    type Error = CsvCodecError;
+
+    fn encode(&mut self, fields: Vec<String>, buf: &mut BytesMut) -> Result<(), CsvCodecError> {
+        let mut writer = csv::WriterBuilder::new()
+            .terminator(csv::Terminator::CRLF)
+            .from_writer(Vec::new());
+        try!(writer.write_record(&fields));
+        let record = match writer.into_inner() {
+            Ok(record) => record,
+            Err(e) => return Err(CsvCodecError::Io(e.into_error())),
+        };
+        buf.reserve(record.len());
+        buf.put(record);
+        Ok(())
+    }
+}
+
+impl From<io::Error> for CsvCodecError {
+    fn from(e: io::Error) -> CsvCodecError {
+        CsvCodecError::Io(e)
+    }
+}
+
+impl From<csv::Error> for CsvCodecError {
+    fn from(e: csv::Error) -> CsvCodecError {
+        CsvCodecError::ParseError(e)
+    }
+}
+
+impl fmt::Display for CsvCodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CsvCodecError::Io(ref e) => write!(f, "I/O error: {}", e),
+            CsvCodecError::ParseError(ref e) => write!(f, "CSV error: {}", e),
+        }
+    }
+}
+
+impl error::Error for CsvCodecError {
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            CsvCodecError::Io(ref e) => Some(e),
+            CsvCodecError::ParseError(ref e) => Some(e),
+        }
+    }
+}
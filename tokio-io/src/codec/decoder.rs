@@ -1,5 +1,13 @@
 use std::io;
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
+
+use codec::and_then_decoder::{self, AndThenDecoder};
+use codec::filter_map_decoder::{self, FilterMapDecoder};
+use codec::map_decoder::{self, MapDecoder};
+use codec::map_err::MapErr;
+
+/// The default value returned by `Decoder::max_buf_size`.
+pub const DEFAULT_MAX_BUF_SIZE: usize = 8 * 1024 * 1024;
 
 /// Decoding of frames via buffers.
 ///
@@ -83,4 +91,219 @@ pub trait Decoder {
             }
         }
     }
+
+    /// Returns the maximum number of bytes which may be buffered while
+    /// waiting for this decoder to produce a frame.
+    ///
+    /// `FramedRead` checks this limit every time `decode` returns `Ok(None)`
+    /// after more data was read. If the buffer holds more bytes than the limit
+    /// at that point, the stream fails with an `io::Error` of kind `Other`
+    /// instead of reading even more data. This protects against peers which
+    /// never send the delimiter or the rest of a frame.
+    ///
+    /// Defaults to 8MiB.
+    fn max_buf_size(&self) -> usize {
+        DEFAULT_MAX_BUF_SIZE
+    }
+
+    /// Decodes a frame from a `Bytes` value.
+    ///
+    /// This is a convenience for one-shot deserialization outside of a framed
+    /// transport. The bytes are converted into a `BytesMut`, which doesn't
+    /// copy them if `src` is the only handle to its data, and passed to
+    /// `decode`. Any bytes left over after decoding a frame are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_io;
+    /// # extern crate bytes;
+    /// use tokio_io::codec::{Decoder, LinesCodec, LinesCodecError};
+    /// use bytes::Bytes;
+    ///
+    /// # pub fn main() {
+    /// let mut codec = LinesCodec::new();
+    /// let line = codec.decode_from_bytes(Bytes::from("hello\n")).unwrap();
+    /// assert_eq!(line, Some("hello".to_string()));
+    /// # }
+    /// ```
+    fn decode_from_bytes(&mut self, src: Bytes) -> Result<Option<Self::Item>, Self::Error> {
+        let mut buf = BytesMut::from(src);
+        self.decode(&mut buf)
+    }
+
+    /// Creates a decoder which transforms every decoded frame with `f`.
+    ///
+    /// This allows post-processing frames, e.g. parsing the lines produced by
+    /// a `LinesCodec`, without writing a new `Decoder`. If the decoder is also
+    /// an `Encoder`, the returned `MapDecoder` encodes frames unchanged, so it
+    /// can still be used with `Framed`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_io;
+    /// # extern crate bytes;
+    /// use tokio_io::codec::{Decoder, LinesCodec, LinesCodecError};
+    /// use bytes::BytesMut;
+    ///
+    /// # pub fn main() {
+    /// let mut codec = LinesCodec::new().map(|line| line.len());
+    /// let mut buf = BytesMut::from(&b"hello\n"[..]);
+    /// assert_eq!(codec.decode(&mut buf).unwrap(), Some(5));
+    /// # }
+    /// ```
+    fn map<F, U>(self, f: F) -> MapDecoder<Self, F>
+        where F: FnMut(Self::Item) -> U,
+              Self: Sized,
+    {
+        map_decoder::new(self, f)
+    }
+
+    /// Creates a decoder which transforms every decoded frame with `f`,
+    /// skipping the frames for which it returns `None`.
+    ///
+    /// This is like [`map`], but allows dropping frames which aren't of
+    /// interest, e.g. keep-alive messages, without surfacing them to the
+    /// consumer of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_io;
+    /// # extern crate bytes;
+    /// use tokio_io::codec::{Decoder, LinesCodec, LinesCodecError};
+    /// use bytes::BytesMut;
+    ///
+    /// # pub fn main() {
+    /// let mut codec = LinesCodec::new().filter_map(|line| line.parse::<u32>().ok());
+    /// let mut buf = BytesMut::from(&b"ping\n42\n"[..]);
+    /// assert_eq!(codec.decode(&mut buf).unwrap(), Some(42));
+    /// # }
+    /// ```
+    ///
+    /// [`map`]: #method.map
+    fn filter_map<F, U>(self, f: F) -> FilterMapDecoder<Self, F>
+        where F: FnMut(Self::Item) -> Option<U>,
+              Self: Sized,
+    {
+        filter_map_decoder::new(self, f)
+    }
+
+    /// Creates a decoder which transforms every decoding error with `f`.
+    ///
+    /// This allows wrapping the errors of a codec in a protocol's own error
+    /// type without writing a new `Decoder`. If the decoder is also an
+    /// `Encoder` with the same error type, the returned `MapErr` maps
+    /// encoding errors as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_io;
+    /// # extern crate bytes;
+    /// use std::io;
+    /// use tokio_io::codec::{Decoder, LinesCodec, LinesCodecError};
+    /// use bytes::BytesMut;
+    ///
+    /// #[derive(Debug)]
+    /// struct ProtocolError(LinesCodecError);
+    ///
+    /// impl From<io::Error> for ProtocolError {
+    ///     fn from(e: io::Error) -> ProtocolError {
+    ///         ProtocolError(LinesCodecError::Io(e))
+    ///     }
+    /// }
+    ///
+    /// # pub fn main() {
+    /// let mut codec = LinesCodec::new().map_err(ProtocolError);
+    /// let mut buf = BytesMut::from(&b"\xff\n"[..]);
+    /// match codec.decode(&mut buf).unwrap_err() {
+    ///     ProtocolError(LinesCodecError::Io(e)) => {
+    ///         assert_eq!(e.kind(), io::ErrorKind::InvalidData)
+    ///     }
+    ///     e => panic!("unexpected error: {:?}", e),
+    /// }
+    /// # }
+    /// ```
+    fn map_err<F, E>(self, f: F) -> MapErr<Self, F>
+        where F: FnMut(Self::Error) -> E,
+              E: From<io::Error>,
+              Self: Sized,
+    {
+        MapErr::new(self, f)
+    }
+
+    /// Creates a decoder which decodes frames in pairs, using a second
+    /// decoder created from the first frame of each pair.
+    ///
+    /// Once this decoder produced a frame, `f` is called with it to create
+    /// the decoder for the frame following it, e.g. one which reads a body
+    /// whose length is given in a header. The returned decoder yields both
+    /// frames as a tuple, and then starts over with this decoder for the next
+    /// pair.
+    ///
+    /// If the stream ends after the first frame of a pair, decoding fails with
+    /// an `io::Error` of kind `UnexpectedEof`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_io;
+    /// # extern crate bytes;
+    /// use tokio_io::codec::{Decoder, LinesCodec, LinesCodecError};
+    /// use tokio_io::codec::length_delimited::LengthDelimitedCodec;
+    /// use bytes::BytesMut;
+    ///
+    /// # pub fn main() {
+    /// // A line naming the message, followed by a length delimited payload
+    /// let mut codec = LinesCodec::new().and_then(|_: &String| LengthDelimitedCodec::new());
+    /// let mut buf = BytesMut::from(&b"greeting\n\x00\x00\x00\x02hi"[..]);
+    /// let (name, payload) = codec.decode(&mut buf).unwrap().unwrap();
+    /// assert_eq!(name, "greeting");
+    /// assert_eq!(payload, "hi");
+    /// # }
+    /// ```
+    fn and_then<D, F>(self, f: F) -> AndThenDecoder<Self, D, F>
+        where D: Decoder,
+              F: FnMut(&Self::Item) -> D,
+              Self::Error: From<D::Error>,
+              Self: Sized,
+    {
+        and_then_decoder::new(self, f)
+    }
+}
+
+impl<D: ?Sized + Decoder> Decoder for Box<D> {
+    type Item = D::Item;
+    type Error = D::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<D::Item>, D::Error> {
+        (**self).decode(src)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<D::Item>, D::Error> {
+        (**self).decode_eof(buf)
+    }
+
+    fn max_buf_size(&self) -> usize {
+        (**self).max_buf_size()
+    }
+}
+
+impl<'a, D: ?Sized + Decoder> Decoder for &'a mut D {
+    type Item = D::Item;
+    type Error = D::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<D::Item>, D::Error> {
+        (**self).decode(src)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<D::Item>, D::Error> {
+        (**self).decode_eof(buf)
+    }
+
+    fn max_buf_size(&self) -> usize {
+        (**self).max_buf_size()
+    }
 }
@@ -0,0 +1,140 @@
+use bytes::BytesMut;
+use std::cmp;
+
+// Splits a buffer into frames terminated by a delimiter byte, discarding
+// frames which exceed a maximum length. Shared by the codecs for text based
+// framings, such as `LinesCodec` and `NullDelimitedCodec`.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Delimited {
+    delimiter: u8,
+
+    // Stored index of the next index to examine for the delimiter.
+    // This is used to optimize searching.
+    // For example, if `decode` was called with `abc`, it would hold `3`,
+    // because that is the next index to examine.
+    // The next time `decode` is called with `abcde\n`, the method will
+    // only look at `de\n` before returning.
+    next_index: usize,
+
+    // The maximum length for a given frame. If `usize::MAX`, frames will be
+    // read until the delimiter is reached.
+    max_length: usize,
+
+    // Are we currently discarding the remainder of a frame which was over
+    // the length limit?
+    is_discarding: bool,
+
+    // A byte right before the delimiter which doesn't count towards
+    // max_length, such as the `\r` of a `\r\n`.
+    ignored: Option<u8>,
+}
+
+impl Delimited {
+    pub fn new(delimiter: u8, max_length: usize) -> Delimited {
+        Delimited {
+            delimiter: delimiter,
+            next_index: 0,
+            max_length: max_length,
+            is_discarding: false,
+            ignored: None,
+        }
+    }
+
+    // Doesn't count `byte` towards the maximum length when it's right before
+    // the delimiter. It's still part of the frame returned.
+    pub fn ignore_before_delimiter(mut self, byte: u8) -> Delimited {
+        self.ignored = Some(byte);
+        self
+    }
+
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    // Forgets how far `buf` was searched, for when the caller takes the data
+    // out of it on its own.
+    pub fn reset(&mut self) {
+        self.next_index = 0;
+    }
+
+    // The length of `frame` which counts towards the maximum length.
+    fn frame_len(&self, frame: &[u8]) -> usize {
+        match (self.ignored, frame.last()) {
+            (Some(ignored), Some(&last)) if ignored == last => frame.len() - 1,
+            _ => frame.len(),
+        }
+    }
+
+    // Returns the next frame, without its delimiter. A frame exceeding the
+    // maximum length results in the error returned by `length_error`, after
+    // which the rest of it is discarded up to the next delimiter.
+    pub fn decode<F, E>(&mut self, buf: &mut BytesMut, length_error: F)
+                        -> Result<Option<BytesMut>, E>
+        where F: FnOnce() -> E,
+    {
+        // A frame of max_length bytes may be followed by the ignored byte as
+        // well as the delimiter.
+        let slack = if self.ignored.is_some() { 2 } else { 1 };
+        loop {
+            // Determine how far into the buffer we'll search for a delimiter.
+            // If there's no max_length set, we'll read to the end of the
+            // buffer.
+            let read_to = cmp::min(self.max_length.saturating_add(slack), buf.len());
+
+            let delimiter = self.delimiter;
+            let delimiter_offset = buf[self.next_index..read_to]
+                .iter()
+                .position(|b| *b == delimiter);
+
+            match (self.is_discarding, delimiter_offset) {
+                (true, Some(offset)) => {
+                    // If we found a delimiter, discard up to that offset and
+                    // then stop discarding. On the next iteration, we'll try
+                    // to read a frame normally.
+                    let _ = buf.split_to(offset + self.next_index + 1);
+                    self.is_discarding = false;
+                    self.next_index = 0;
+                }
+                (true, None) => {
+                    // Otherwise, we didn't find a delimiter, so we'll discard
+                    // everything we read. On the next iteration, we'll
+                    // continue discarding up to max_len bytes unless we find
+                    // a delimiter.
+                    let _ = buf.split_to(read_to);
+                    self.next_index = 0;
+                    if buf.is_empty() {
+                        return Ok(None);
+                    }
+                }
+                (false, Some(offset)) => {
+                    // Found a frame!
+                    let delimiter_index = offset + self.next_index;
+                    self.next_index = 0;
+                    let mut frame = buf.split_to(delimiter_index + 1);
+                    frame.truncate(delimiter_index);
+                    // Only possible if the byte before the delimiter wasn't
+                    // the ignored one. The frame has been consumed already,
+                    // so there's nothing to discard.
+                    if self.frame_len(&frame) > self.max_length {
+                        return Err(length_error());
+                    }
+                    return Ok(Some(frame));
+                }
+                (false, None) if self.frame_len(buf) > self.max_length => {
+                    // Reached the maximum length without finding a
+                    // delimiter, return an error and start discarding on the
+                    // next call.
+                    self.is_discarding = true;
+                    return Err(length_error());
+                }
+                (false, None) => {
+                    // We didn't find a frame or reach the length limit, so
+                    // the next call will resume searching at the current
+                    // offset.
+                    self.next_index = read_to;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
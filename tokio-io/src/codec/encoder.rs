@@ -1,5 +1,7 @@
 use std::io;
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
+
+use codec::with_encoder::{self, WithEncoder};
 
 /// Trait of helper objects to write out messages as bytes, for use with
 /// `FramedWrite`.
@@ -20,4 +22,84 @@ pub trait Encoder {
     /// will be written out when possible.
     fn encode(&mut self, item: Self::Item, dst: &mut BytesMut)
               -> Result<(), Self::Error>;
+
+    /// Encodes a single frame into a new `Bytes` value.
+    ///
+    /// This is a convenience for serializing an item outside of a framed
+    /// transport. The item is encoded into a fresh buffer which is then
+    /// frozen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio_io::codec::{Encoder, LinesCodec};
+    ///
+    /// let mut codec = LinesCodec::new();
+    /// let bytes = codec.encode_to_bytes("hello".to_string()).unwrap();
+    /// assert_eq!(bytes, "hello\n");
+    /// ```
+    fn encode_to_bytes(&mut self, item: Self::Item) -> Result<Bytes, Self::Error> {
+        let mut dst = BytesMut::new();
+        try!(self.encode(item, &mut dst));
+        Ok(dst.freeze())
+    }
+
+    /// Encodes every item of `items` into the buffer provided, returning the
+    /// total number of bytes appended to `dst`.
+    ///
+    /// This allows batching many small frames into a single buffer, which
+    /// can then be written out with a single write. If encoding an item
+    /// fails, the error is returned right away and the frames encoded so far
+    /// are left in `dst`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate bytes;
+    /// # extern crate tokio_io;
+    /// use bytes::BytesMut;
+    /// use tokio_io::codec::{Encoder, LinesCodec};
+    ///
+    /// # fn main() {
+    /// let mut codec = LinesCodec::new();
+    /// let mut buf = BytesMut::new();
+    /// let lines = vec!["one".to_string(), "two".to_string()];
+    /// assert_eq!(codec.encode_all(lines, &mut buf).unwrap(), 8);
+    /// assert_eq!(buf, "one\ntwo\n");
+    /// # }
+    /// ```
+    fn encode_all<I>(&mut self, items: I, dst: &mut BytesMut) -> Result<usize, Self::Error>
+        where I: IntoIterator<Item = Self::Item>,
+              Self: Sized,
+    {
+        let start = dst.len();
+        for item in items {
+            try!(self.encode(item, dst));
+        }
+        Ok(dst.len() - start)
+    }
+
+    /// Creates an encoder which transforms every item with `f` before
+    /// encoding it with this encoder.
+    ///
+    /// This is the counterpart of [`Decoder::map`], e.g. for encoding a
+    /// protocol's message type with a generic codec. If the encoder is also a
+    /// `Decoder`, the returned `WithEncoder` decodes frames unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio_io::codec::{Encoder, LinesCodec};
+    ///
+    /// let mut codec = LinesCodec::new().with(|n: u32| n.to_string());
+    /// assert_eq!(codec.encode_to_bytes(42).unwrap(), "42\n");
+    /// ```
+    ///
+    /// [`Decoder::map`]: trait.Decoder.html#method.map
+    fn with<F, U>(self, f: F) -> WithEncoder<Self, F, U>
+        where F: FnMut(U) -> Self::Item,
+              Self: Sized,
+    {
+        with_encoder::new(self, f)
+    }
 }
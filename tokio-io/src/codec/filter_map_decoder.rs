@@ -0,0 +1,87 @@
+use std::fmt;
+
+use bytes::BytesMut;
+use codec::{Decoder, Encoder};
+
+/// A `Decoder` which transforms the frames of another decoder with a closure,
+/// skipping frames for which it returns `None`.
+///
+/// Created by the [`Decoder::filter_map`] method.
+///
+/// [`Decoder::filter_map`]: trait.Decoder.html#method.filter_map
+#[derive(Clone)]
+pub struct FilterMapDecoder<D, F> {
+    decoder: D,
+    f: F,
+}
+
+pub fn new<D, F>(decoder: D, f: F) -> FilterMapDecoder<D, F> {
+    FilterMapDecoder {
+        decoder: decoder,
+        f: f,
+    }
+}
+
+impl<D, F> FilterMapDecoder<D, F> {
+    /// Returns a reference to the underlying decoder.
+    pub fn get_ref(&self) -> &D {
+        &self.decoder
+    }
+
+    /// Returns a mutable reference to the underlying decoder.
+    pub fn get_mut(&mut self) -> &mut D {
+        &mut self.decoder
+    }
+
+    /// Consumes the `FilterMapDecoder`, returning the underlying decoder.
+    pub fn into_inner(self) -> D {
+        self.decoder
+    }
+}
+
+impl<D, F, U> Decoder for FilterMapDecoder<D, F>
+    where D: Decoder,
+          F: FnMut(D::Item) -> Option<U>,
+{
+    type Item = U;
+    type Error = D::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<U>, D::Error> {
+        while let Some(item) = try!(self.decoder.decode(buf)) {
+            if let Some(item) = (self.f)(item) {
+                return Ok(Some(item));
+            }
+        }
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<U>, D::Error> {
+        while let Some(item) = try!(self.decoder.decode_eof(buf)) {
+            if let Some(item) = (self.f)(item) {
+                return Ok(Some(item));
+            }
+        }
+        Ok(None)
+    }
+
+    fn max_buf_size(&self) -> usize {
+        self.decoder.max_buf_size()
+    }
+}
+
+impl<D: Encoder, F> Encoder for FilterMapDecoder<D, F> {
+    type Item = D::Item;
+    type Error = D::Error;
+
+    fn encode(&mut self, item: D::Item, dst: &mut BytesMut) -> Result<(), D::Error> {
+        self.decoder.encode(item, dst)
+    }
+}
+
+impl<D: fmt::Debug, F> fmt::Debug for FilterMapDecoder<D, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FilterMapDecoder")
+            .field("decoder", &self.decoder)
+            .finish()
+    }
+}
@@ -0,0 +1,60 @@
+use bytes::{BufMut, BytesMut};
+use codec::{Encoder, Decoder};
+use std::io;
+
+/// A `Codec` for heartbeat frames which carry no payload, such as a
+/// WebSocket ping or an MQTT `PINGREQ`.
+///
+/// Every `()` item is encoded as a fixed byte sequence, and the decoder
+/// recognizes and discards that sequence, yielding `()` for each occurrence.
+/// Any other data results in an `io::Error` of kind `InvalidData`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HeartbeatCodec {
+    frame: &'static [u8],
+}
+
+impl HeartbeatCodec {
+    /// Returns a `HeartbeatCodec` which sends and expects `frame` as the
+    /// heartbeat.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame` is empty.
+    pub fn new(frame: &'static [u8]) -> HeartbeatCodec {
+        assert!(!frame.is_empty(), "heartbeat frame must not be empty");
+        HeartbeatCodec { frame: frame }
+    }
+
+    /// Returns the byte sequence of a heartbeat frame.
+    pub fn frame(&self) -> &'static [u8] {
+        self.frame
+    }
+}
+
+impl Decoder for HeartbeatCodec {
+    type Item = ();
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<()>, io::Error> {
+        let len = self.frame.len();
+        if buf.len() >= len && &buf[..len] == self.frame {
+            let _ = buf.split_to(len);
+            return Ok(Some(()));
+        }
+        if buf.len() < len && self.frame.starts_with(&buf[..]) {
+            return Ok(None);
+        }
+        Err(io::Error::new(io::ErrorKind::InvalidData, "expected heartbeat frame"))
+    }
+}
+
+impl Encoder for HeartbeatCodec {
+    type Item = ();
+    type Error = io::Error;
+
+    fn encode(&mut self, _: (), buf: &mut BytesMut) -> Result<(), io::Error> {
+        buf.reserve(self.frame.len());
+        buf.put(self.frame);
+        Ok(())
+    }
+}
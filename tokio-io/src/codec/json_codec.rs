@@ -0,0 +1,155 @@
+use std::{error, fmt, io};
+use std::marker::PhantomData;
+
+use bytes::{BufMut, BytesMut};
+use codec::{Decoder, Encoder, LinesCodec};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json;
+
+/// A `Codec` implementation that frames values of type `T` as
+/// newline-delimited JSON.
+///
+/// Each value is serialized on a line of its own, terminated by `\n`. When
+/// decoding, every complete line is deserialized into a `T`; empty lines are
+/// skipped.
+///
+/// This codec is only available when the `json` feature is enabled.
+pub struct JsonCodec<T> {
+    lines: LinesCodec,
+    _marker: PhantomData<fn(T) -> T>,
+}
+
+/// An error which occurred while encoding or decoding JSON values with a
+/// [`JsonCodec`].
+///
+/// [`JsonCodec`]: struct.JsonCodec.html
+#[derive(Debug)]
+pub enum JsonCodecError {
+    /// An I/O error, including data which wasn't valid UTF-8.
+    Io(io::Error),
+    /// A value couldn't be serialized, or a line couldn't be deserialized.
+    Json(serde_json::Error),
+}
+
+impl<T> JsonCodec<T>
+    where T: Serialize + DeserializeOwned,
+{
+    /// Returns a `JsonCodec` for encoding and decoding values of type `T`.
+    pub fn new() -> JsonCodec<T> {
+        JsonCodec {
+            lines: LinesCodec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the value returned by `Decoder::max_buf_size`.
+    ///
+    /// This bounds how much data `FramedRead` buffers while waiting for the
+    /// end of a line.
+    pub fn with_max_buf_size(self, max_buf_size: usize) -> JsonCodec<T> {
+        JsonCodec {
+            lines: self.lines.with_max_buf_size(max_buf_size),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Decoder for JsonCodec<T>
+    where T: Serialize + DeserializeOwned,
+{
+    type Item = T;
+    type Error = JsonCodecError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<T>, JsonCodecError> {
+        while let Some(line) = try!(self.lines.decode(buf).map_err(io::Error::from)) {
+            if let Some(value) = try!(parse(&line)) {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<T>, JsonCodecError> {
+        while let Some(line) = try!(self.lines.decode_eof(buf).map_err(io::Error::from)) {
+            if let Some(value) = try!(parse(&line)) {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    fn max_buf_size(&self) -> usize {
+        self.lines.max_buf_size()
+    }
+}
+
+// Deserializes a single line, returning `None` for lines that are blank.
+fn parse<T: DeserializeOwned>(line: &str) -> Result<Option<T>, JsonCodecError> {
+    if line.trim().is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(try!(serde_json::from_str(line))))
+}
+
+impl<T> Encoder for JsonCodec<T>
+    where T: Serialize + DeserializeOwned,
+{
+    type Item = T;
+    type Error = JsonCodecError;
+
+    fn encode(&mut self, item: T, buf: &mut BytesMut) -> Result<(), JsonCodecError> {
+        let json = try!(serde_json::to_vec(&item));
+        buf.reserve(json.len() + 1);
+        buf.put(json);
+        buf.put_u8(b'\n');
+        Ok(())
+    }
+}
+
+impl<T> Clone for JsonCodec<T> {
+    fn clone(&self) -> JsonCodec<T> {
+        JsonCodec {
+            lines: self.lines.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for JsonCodec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JsonCodec")
+            .field("lines", &self.lines)
+            .finish()
+    }
+}
+
+impl From<io::Error> for JsonCodecError {
+    fn from(e: io::Error) -> JsonCodecError {
+        JsonCodecError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for JsonCodecError {
+    fn from(e: serde_json::Error) -> JsonCodecError {
+        JsonCodecError::Json(e)
+    }
+}
+
+impl fmt::Display for JsonCodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            JsonCodecError::Io(ref e) => write!(f, "I/O error: {}", e),
+            JsonCodecError::Json(ref e) => write!(f, "JSON error: {}", e),
+        }
+    }
+}
+
+impl error::Error for JsonCodecError {
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            JsonCodecError::Io(ref e) => Some(e),
+            JsonCodecError::Json(ref e) => Some(e),
+        }
+    }
+}
@@ -1,23 +1,107 @@
 use bytes::{BufMut, BytesMut};
 use codec::{Encoder, Decoder};
-use std::{io, str};
+use codec::decoder::DEFAULT_MAX_BUF_SIZE;
+use codec::delimited::Delimited;
+use std::{error, fmt, io, str, usize};
 
 /// A simple `Codec` implementation that splits up data into lines.
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct LinesCodec {
-    // Stored index of the next index to examine for a `\n` character.
-    // This is used to optimize searching.
-    // For example, if `decode` was called with `abc`, it would hold `3`,
-    // because that is the next index to examine.
-    // The next time `decode` is called with `abcde\n`, the method will
-    // only look at `de\n` before returning.
-    next_index: usize,
+    /// Splits the input at `\n` characters, up to a maximum line length.
+    delimited: Delimited,
+
+    /// The value returned by `Decoder::max_buf_size`.
+    max_buf_size: usize,
+}
+
+/// An error which occurred while encoding or decoding lines with a
+/// [`LinesCodec`].
+///
+/// [`LinesCodec`]: struct.LinesCodec.html
+#[derive(Debug)]
+pub enum LinesCodecError {
+    /// A line was longer than the codec's maximum line length. The line is
+    /// discarded, and decoding continues with the next one.
+    MaxLineLengthExceeded,
+    /// An I/O error, including data which wasn't valid UTF-8.
+    Io(io::Error),
 }
 
 impl LinesCodec {
     /// Returns a `LinesCodec` for splitting up data into lines.
+    ///
+    /// # Note
+    ///
+    /// The returned `LinesCodec` doesn't limit the length of a line itself,
+    /// but `Decoder::max_buf_size` defaults to 8MiB, so a `FramedRead` fails
+    /// with a "decoder buffer overflow" error once more than that is buffered
+    /// without a `\n`. Use [`with_max_buf_size`] to change that bound, or
+    /// see [`new_with_max_length`] for limiting and skipping long lines.
+    ///
+    /// [`with_max_buf_size`]: #method.with_max_buf_size
+    /// [`new_with_max_length`]: #method.new_with_max_length
     pub fn new() -> LinesCodec {
-        LinesCodec { next_index: 0 }
+        LinesCodec {
+            delimited: Delimited::new(b'\n', usize::MAX).ignore_before_delimiter(b'\r'),
+            max_buf_size: DEFAULT_MAX_BUF_SIZE,
+        }
+    }
+
+    /// Returns a `LinesCodec` with a maximum line length limit.
+    ///
+    /// If this is set, calls to `LinesCodec::decode` will return
+    /// [`LinesCodecError::MaxLineLengthExceeded`] when a line exceeds the
+    /// length limit. The `\r` of a line ending in `\r\n` doesn't count
+    /// towards the limit. Subsequent calls will discard up to `max_length` bytes from that line
+    /// until a newline character is reached, returning `None` until the line
+    /// over the limit has been fully discarded. After that point, calls to
+    /// `decode` will function as normal.
+    ///
+    /// # Note
+    ///
+    /// Setting a length limit is highly recommended for any `LinesCodec` which
+    /// will be exposed to untrusted input. Otherwise, the buffer that holds
+    /// the line currently being read is only bounded by `max_buf_size`, 8MiB
+    /// by default, and an attacker could make every connection buffer that
+    /// much by sending input without any `\n` characters. Hitting that bound
+    /// also fails the whole `FramedRead`, rather than skipping the line.
+    ///
+    /// [`LinesCodecError::MaxLineLengthExceeded`]: enum.LinesCodecError.html#variant.MaxLineLengthExceeded
+    pub fn new_with_max_length(max_length: usize) -> LinesCodec {
+        LinesCodec {
+            delimited: Delimited::new(b'\n', max_length).ignore_before_delimiter(b'\r'),
+            ..LinesCodec::new()
+        }
+    }
+
+    /// Returns the maximum line length when decoding.
+    ///
+    /// ```
+    /// use std::usize;
+    /// use tokio_io::codec::LinesCodec;
+    ///
+    /// let codec = LinesCodec::new();
+    /// assert_eq!(codec.max_length(), usize::MAX);
+    /// ```
+    /// ```
+    /// use tokio_io::codec::LinesCodec;
+    ///
+    /// let codec = LinesCodec::new_with_max_length(256);
+    /// assert_eq!(codec.max_length(), 256);
+    /// ```
+    pub fn max_length(&self) -> usize {
+        self.delimited.max_length()
+    }
+
+    /// Sets the value returned by `Decoder::max_buf_size`.
+    ///
+    /// This bounds how much data `FramedRead` buffers while waiting for a
+    /// `\n`, independently of whether a `max_length` is set.
+    pub fn with_max_buf_size(self, max_buf_size: usize) -> LinesCodec {
+        LinesCodec {
+            max_buf_size: max_buf_size,
+            ..self
+        }
     }
 }
 
@@ -28,6 +112,10 @@ fn utf8(buf: &[u8]) -> Result<&str, io::Error> {
             "Unable to decode input as UTF8"))
 }
 
+fn length_error() -> LinesCodecError {
+    LinesCodecError::MaxLineLengthExceeded
+}
+
 fn without_carriage_return(s: &[u8]) -> &[u8] {
     if let Some(&b'\r') = s.last() {
         &s[..s.len() - 1]
@@ -38,26 +126,20 @@ fn without_carriage_return(s: &[u8]) -> &[u8] {
 
 impl Decoder for LinesCodec {
     type Item = String;
-    type Error = io::Error;
-
-    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<String>, io::Error> {
-        if let Some(newline_offset) =
-            buf[self.next_index..].iter().position(|b| *b == b'\n')
-        {
-            let newline_index = newline_offset + self.next_index;
-            let line = buf.split_to(newline_index + 1);
-            let line = &line[..line.len()-1];
-            let line = without_carriage_return(line);
-            let line = utf8(line)?;
-            self.next_index = 0;
-            Ok(Some(line.to_string()))
-        } else {
-            self.next_index = buf.len();
-            Ok(None)
+    type Error = LinesCodecError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<String>, LinesCodecError> {
+        match self.delimited.decode(buf, length_error)? {
+            Some(line) => {
+                let line = without_carriage_return(&line);
+                let line = utf8(line)?;
+                Ok(Some(line.to_string()))
+            }
+            None => Ok(None),
         }
     }
 
-    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<String>, io::Error> {
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<String>, LinesCodecError> {
         Ok(match self.decode(buf)? {
             Some(frame) => Some(frame),
             None => {
@@ -68,22 +150,61 @@ impl Decoder for LinesCodec {
                     let line = buf.take();
                     let line = without_carriage_return(&line);
                     let line = utf8(line)?;
-                    self.next_index = 0;
+                    self.delimited.reset();
                     Some(line.to_string())
                 }
             }
         })
     }
+
+    fn max_buf_size(&self) -> usize {
+        self.max_buf_size
+    }
 }
 
 impl Encoder for LinesCodec {
     type Item = String;
-    type Error = io::Error;
+    type Error = LinesCodecError;
 
-    fn encode(&mut self, line: String, buf: &mut BytesMut) -> Result<(), io::Error> {
+    fn encode(&mut self, line: String, buf: &mut BytesMut) -> Result<(), LinesCodecError> {
         buf.reserve(line.len() + 1);
         buf.put(line);
         buf.put_u8(b'\n');
         Ok(())
     }
 }
+
+impl From<io::Error> for LinesCodecError {
+    fn from(e: io::Error) -> LinesCodecError {
+        LinesCodecError::Io(e)
+    }
+}
+
+impl From<LinesCodecError> for io::Error {
+    fn from(e: LinesCodecError) -> io::Error {
+        match e {
+            LinesCodecError::MaxLineLengthExceeded => {
+                io::Error::new(io::ErrorKind::InvalidData, "line length limit exceeded")
+            }
+            LinesCodecError::Io(e) => e,
+        }
+    }
+}
+
+impl fmt::Display for LinesCodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LinesCodecError::MaxLineLengthExceeded => f.write_str("line length limit exceeded"),
+            LinesCodecError::Io(ref e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl error::Error for LinesCodecError {
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            LinesCodecError::MaxLineLengthExceeded => None,
+            LinesCodecError::Io(ref e) => Some(e),
+        }
+    }
+}
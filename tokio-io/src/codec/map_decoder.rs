@@ -0,0 +1,76 @@
+use std::fmt;
+
+use bytes::BytesMut;
+use codec::{Decoder, Encoder};
+
+/// A `Decoder` which transforms the frames of another decoder with a closure.
+///
+/// Created by the [`Decoder::map`] method.
+///
+/// [`Decoder::map`]: trait.Decoder.html#method.map
+#[derive(Clone)]
+pub struct MapDecoder<D, F> {
+    decoder: D,
+    f: F,
+}
+
+pub fn new<D, F>(decoder: D, f: F) -> MapDecoder<D, F> {
+    MapDecoder {
+        decoder: decoder,
+        f: f,
+    }
+}
+
+impl<D, F> MapDecoder<D, F> {
+    /// Returns a reference to the underlying decoder.
+    pub fn get_ref(&self) -> &D {
+        &self.decoder
+    }
+
+    /// Returns a mutable reference to the underlying decoder.
+    pub fn get_mut(&mut self) -> &mut D {
+        &mut self.decoder
+    }
+
+    /// Consumes the `MapDecoder`, returning the underlying decoder.
+    pub fn into_inner(self) -> D {
+        self.decoder
+    }
+}
+
+impl<D, F, U> Decoder for MapDecoder<D, F>
+    where D: Decoder,
+          F: FnMut(D::Item) -> U,
+{
+    type Item = U;
+    type Error = D::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<U>, D::Error> {
+        Ok(try!(self.decoder.decode(buf)).map(&mut self.f))
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<U>, D::Error> {
+        Ok(try!(self.decoder.decode_eof(buf)).map(&mut self.f))
+    }
+
+    fn max_buf_size(&self) -> usize {
+        self.decoder.max_buf_size()
+    }
+}
+
+impl<D: Encoder, F> Encoder for MapDecoder<D, F> {
+    type Item = D::Item;
+    type Error = D::Error;
+
+    fn encode(&mut self, item: D::Item, dst: &mut BytesMut) -> Result<(), D::Error> {
+        self.decoder.encode(item, dst)
+    }
+}
+
+impl<D: fmt::Debug, F> fmt::Debug for MapDecoder<D, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MapDecoder")
+            .field("decoder", &self.decoder)
+            .finish()
+    }
+}
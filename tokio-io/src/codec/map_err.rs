@@ -0,0 +1,118 @@
+use std::{fmt, io};
+
+use bytes::BytesMut;
+use codec::{Decoder, Encoder};
+
+/// A codec which transforms the errors of another codec with a closure.
+///
+/// Errors are mapped for both decoding and encoding, as long as the codec's
+/// `Decoder` and `Encoder` error types can both be passed to the closure.
+/// Created by [`Decoder::map_err`] or [`MapErr::new`].
+///
+/// [`Decoder::map_err`]: trait.Decoder.html#method.map_err
+/// [`MapErr::new`]: #method.new
+#[derive(Clone)]
+pub struct MapErr<C, F> {
+    codec: C,
+    f: F,
+}
+
+impl<C, F> MapErr<C, F> {
+    /// Creates a codec which transforms every error of `codec` with `f`.
+    ///
+    /// This allows wrapping the errors of a codec in a protocol's own error
+    /// type without writing a new `Decoder` or `Encoder`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_io;
+    /// # extern crate bytes;
+    /// use std::io;
+    /// use tokio_io::codec::{Decoder, LinesCodec, LinesCodecError, MapErr};
+    /// use bytes::BytesMut;
+    ///
+    /// #[derive(Debug)]
+    /// struct ProtocolError(LinesCodecError);
+    ///
+    /// impl From<io::Error> for ProtocolError {
+    ///     fn from(e: io::Error) -> ProtocolError {
+    ///         ProtocolError(LinesCodecError::Io(e))
+    ///     }
+    /// }
+    ///
+    /// # pub fn main() {
+    /// let mut codec = MapErr::new(LinesCodec::new(), ProtocolError);
+    /// let mut buf = BytesMut::from(&b"\xff\n"[..]);
+    /// match codec.decode(&mut buf).unwrap_err() {
+    ///     ProtocolError(LinesCodecError::Io(e)) => {
+    ///         assert_eq!(e.kind(), io::ErrorKind::InvalidData)
+    ///     }
+    ///     e => panic!("unexpected error: {:?}", e),
+    /// }
+    /// # }
+    /// ```
+    pub fn new(codec: C, f: F) -> MapErr<C, F> {
+        MapErr {
+            codec: codec,
+            f: f,
+        }
+    }
+
+    /// Returns a reference to the underlying codec.
+    pub fn get_ref(&self) -> &C {
+        &self.codec
+    }
+
+    /// Returns a mutable reference to the underlying codec.
+    pub fn get_mut(&mut self) -> &mut C {
+        &mut self.codec
+    }
+
+    /// Consumes the `MapErr`, returning the underlying codec.
+    pub fn into_inner(self) -> C {
+        self.codec
+    }
+}
+
+impl<C, F, E> Decoder for MapErr<C, F>
+    where C: Decoder,
+          F: FnMut(C::Error) -> E,
+          E: From<io::Error>,
+{
+    type Item = C::Item;
+    type Error = E;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<C::Item>, E> {
+        self.codec.decode(buf).map_err(&mut self.f)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<C::Item>, E> {
+        self.codec.decode_eof(buf).map_err(&mut self.f)
+    }
+
+    fn max_buf_size(&self) -> usize {
+        self.codec.max_buf_size()
+    }
+}
+
+impl<C, F, E> Encoder for MapErr<C, F>
+    where C: Encoder,
+          F: FnMut(C::Error) -> E,
+          E: From<io::Error>,
+{
+    type Item = C::Item;
+    type Error = E;
+
+    fn encode(&mut self, item: C::Item, dst: &mut BytesMut) -> Result<(), E> {
+        self.codec.encode(item, dst).map_err(&mut self.f)
+    }
+}
+
+impl<C: fmt::Debug, F> fmt::Debug for MapErr<C, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MapErr")
+            .field("codec", &self.codec)
+            .finish()
+    }
+}
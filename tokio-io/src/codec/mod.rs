@@ -10,17 +10,55 @@
 //! [`Stream`]: #
 //! [transports]: #
 
+mod and_then_decoder;
 mod decoder;
+mod delimited;
 mod encoder;
+mod filter_map_decoder;
+mod heartbeat_codec;
 mod bytes_codec;
 mod lines_codec;
+mod map_decoder;
+mod map_err;
+mod multipart;
+mod null_delimited_codec;
+mod split_codec;
+mod varint_codec;
+mod with_encoder;
+#[cfg(feature = "bincode-codec")]
+mod bincode_codec;
+#[cfg(feature = "csv")]
+mod csv_codec;
+#[cfg(feature = "json")]
+mod json_codec;
+#[cfg(feature = "prost")]
+mod protobuf_codec;
 
+pub use self::and_then_decoder::AndThenDecoder;
 pub use self::decoder::Decoder;
 pub use self::encoder::Encoder;
-pub use self::bytes_codec::BytesCodec;
-pub use self::lines_codec::LinesCodec;
+pub use self::filter_map_decoder::FilterMapDecoder;
+pub use self::heartbeat_codec::HeartbeatCodec;
+pub use self::bytes_codec::{BytesCodec, BytesCodecError, FrozenBytesCodec};
+pub use self::lines_codec::{LinesCodec, LinesCodecError};
+pub use self::map_decoder::MapDecoder;
+pub use self::map_err::MapErr;
+pub use self::multipart::{MultipartDecoder, MultipartError};
+pub use self::null_delimited_codec::NullDelimitedCodec;
+pub use self::split_codec::{Phase, SplitCodec};
+pub use self::varint_codec::VarIntCodec;
+pub use self::with_encoder::WithEncoder;
+#[cfg(feature = "bincode-codec")]
+pub use self::bincode_codec::{BincodeCodec, BincodeCodecConfig, BincodeCodecError};
+#[cfg(feature = "csv")]
+pub use self::csv_codec::{CsvCodec, CsvCodecError};
+#[cfg(feature = "json")]
+pub use self::json_codec::{JsonCodec, JsonCodecError};
+#[cfg(feature = "prost")]
+pub use self::protobuf_codec::{ProtobufCodec, ProtobufCodecError};
 
 pub use framed::{Framed, FramedParts};
+pub use framed_buf_read::FramedBufRead;
 pub use framed_read::FramedRead;
 pub use framed_write::FramedWrite;
 
@@ -357,11 +395,37 @@ pub mod length_delimited {
     //! +------------+--------------+
     //! ```
     //!
+    //! # Using the codec directly
+    //!
+    //! The framing logic is also available as [`LengthDelimitedCodec`], which
+    //! implements both [`Decoder`] and [`Encoder`]. It is configured through
+    //! the same [`Builder`] and can be combined with the generic framing
+    //! adapters in the parent module:
+    //!
+    //! ```
+    //! # extern crate tokio_io;
+    //! # use tokio_io::AsyncRead;
+    //! use tokio_io::codec;
+    //! use tokio_io::codec::length_delimited::LengthDelimitedCodec;
+    //!
+    //! # fn bind_read<T: AsyncRead>(io: T) {
+    //! let codec = LengthDelimitedCodec::builder()
+    //!     .length_field_length(2)
+    //!     .new_codec();
+    //!
+    //! codec::FramedRead::new(io, codec);
+    //! # }
+    //! # pub fn main() {}
+    //! ```
+    //!
     //! [`FramedRead`]: struct.FramedRead.html
     //! [`FramedWrite`]: struct.FramedWrite.html
     //! [`AsyncRead`]: ../../trait.AsyncRead.html
     //! [`AsyncWrite`]: ../../trait.AsyncWrite.html
     //! [`Encoder`]: ../trait.Encoder.html
+    //! [`Decoder`]: ../trait.Decoder.html
+    //! [`Builder`]: struct.Builder.html
+    //! [`LengthDelimitedCodec`]: struct.LengthDelimitedCodec.html
     //! [`BytesMut`]: https://docs.rs/bytes/~0.4/bytes/struct.BytesMut.html
 
     pub use ::length_delimited::*;
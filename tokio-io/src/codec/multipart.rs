@@ -0,0 +1,202 @@
+use std::{error, fmt, io};
+
+use bytes::BytesMut;
+use codec::Decoder;
+
+/// A `Decoder` for MIME multipart bodies, as used by HTTP form uploads.
+///
+/// Each part of the body is yielded as a frame, without its boundary
+/// delimiter and headers. Anything before the first boundary (the prologue)
+/// and after the closing `--boundary--` delimiter (the epilogue) is
+/// discarded.
+///
+/// If the stream ends before the closing delimiter was seen, decoding fails
+/// with [`MultipartError::MissingBoundary`].
+///
+/// [`MultipartError::MissingBoundary`]: enum.MultipartError.html#variant.MissingBoundary
+#[derive(Clone, Debug)]
+pub struct MultipartDecoder {
+    // `\r\n--` followed by the boundary
+    delimiter: Vec<u8>,
+    state: State,
+    // Whether nothing was consumed yet, in which case the first delimiter
+    // doesn't need to be preceded by a line break.
+    at_start: bool,
+    // Offset at which to continue searching for the delimiter in a body.
+    next_index: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum State {
+    Prologue,
+    AfterDelimiter,
+    Headers,
+    Body,
+    Done,
+}
+
+/// An error which occurred while decoding a multipart body with a
+/// [`MultipartDecoder`].
+///
+/// [`MultipartDecoder`]: struct.MultipartDecoder.html
+#[derive(Debug)]
+pub enum MultipartError {
+    /// The stream ended without the closing boundary delimiter.
+    MissingBoundary,
+    /// An I/O error.
+    Io(io::Error),
+}
+
+impl MultipartDecoder {
+    /// Creates a new `MultipartDecoder` for parts separated by `boundary`.
+    ///
+    /// The boundary is given without the leading `--`, exactly as in the
+    /// `boundary` parameter of the `Content-Type` header.
+    pub fn new(boundary: &[u8]) -> MultipartDecoder {
+        let mut delimiter = b"\r\n--".to_vec();
+        delimiter.extend_from_slice(boundary);
+        MultipartDecoder {
+            delimiter: delimiter,
+            state: State::Prologue,
+            at_start: true,
+            next_index: 0,
+        }
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+impl Decoder for MultipartDecoder {
+    type Item = BytesMut;
+    type Error = MultipartError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<BytesMut>, MultipartError> {
+        loop {
+            match self.state {
+                State::Prologue => {
+                    if self.at_start {
+                        // The first delimiter may start the body right away.
+                        let first = &self.delimiter[2..];
+                        if buf.len() < first.len() && first.starts_with(&buf[..]) {
+                            return Ok(None);
+                        }
+                        self.at_start = false;
+                        if buf.starts_with(first) {
+                            let _ = buf.split_to(first.len());
+                            self.state = State::AfterDelimiter;
+                            continue;
+                        }
+                    }
+                    match find(buf, &self.delimiter) {
+                        Some(i) => {
+                            let _ = buf.split_to(i + self.delimiter.len());
+                            self.state = State::AfterDelimiter;
+                        }
+                        None => {
+                            // Discard the prologue, except for what may be
+                            // the start of a delimiter.
+                            let keep = self.delimiter.len() - 1;
+                            let discard = buf.len().saturating_sub(keep);
+                            let _ = buf.split_to(discard);
+                            return Ok(None);
+                        }
+                    }
+                }
+                State::AfterDelimiter => {
+                    if buf.len() < 2 {
+                        return Ok(None);
+                    }
+                    if &buf[..2] == b"--" {
+                        let _ = buf.split_to(2);
+                        self.state = State::Done;
+                        continue;
+                    }
+                    // Skip the rest of the delimiter line, which may contain
+                    // transport padding.
+                    match find(buf, b"\r\n") {
+                        Some(i) => {
+                            let _ = buf.split_to(i + 2);
+                            self.state = State::Headers;
+                        }
+                        None => return Ok(None),
+                    }
+                }
+                State::Headers => {
+                    if buf.len() < 2 {
+                        return Ok(None);
+                    }
+                    // A part without headers starts with an empty line.
+                    if &buf[..2] == b"\r\n" {
+                        let _ = buf.split_to(2);
+                        self.state = State::Body;
+                        continue;
+                    }
+                    match find(buf, b"\r\n\r\n") {
+                        Some(i) => {
+                            let _ = buf.split_to(i + 4);
+                            self.state = State::Body;
+                        }
+                        None => return Ok(None),
+                    }
+                }
+                State::Body => {
+                    match find(&buf[self.next_index..], &self.delimiter) {
+                        Some(i) => {
+                            let body = buf.split_to(self.next_index + i);
+                            let _ = buf.split_to(self.delimiter.len());
+                            self.next_index = 0;
+                            self.state = State::AfterDelimiter;
+                            return Ok(Some(body));
+                        }
+                        None => {
+                            // The next search only needs to look at bytes
+                            // which may be part of a delimiter.
+                            let keep = self.delimiter.len() - 1;
+                            self.next_index = buf.len().saturating_sub(keep);
+                            return Ok(None);
+                        }
+                    }
+                }
+                State::Done => {
+                    // Discard the epilogue
+                    buf.clear();
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<BytesMut>, MultipartError> {
+        match try!(self.decode(buf)) {
+            Some(part) => Ok(Some(part)),
+            None if self.state == State::Done => Ok(None),
+            None => Err(MultipartError::MissingBoundary),
+        }
+    }
+}
+
+impl From<io::Error> for MultipartError {
+    fn from(e: io::Error) -> MultipartError {
+        MultipartError::Io(e)
+    }
+}
+
+impl fmt::Display for MultipartError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MultipartError::MissingBoundary => f.write_str("missing closing boundary"),
+            MultipartError::Io(ref e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl error::Error for MultipartError {
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            MultipartError::MissingBoundary => None,
+            MultipartError::Io(ref e) => Some(e),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use codec::{Encoder, Decoder};
+use codec::delimited::Delimited;
+use std::{io, usize};
+
+/// A simple `Codec` implementation that splits up data into frames
+/// terminated by a NUL (`\0`) byte, as used by C strings and some text
+/// protocols.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct NullDelimitedCodec {
+    /// Splits the input at `\0` bytes, up to a maximum frame length.
+    delimited: Delimited,
+}
+
+impl NullDelimitedCodec {
+    /// Returns a `NullDelimitedCodec` for splitting up data into NUL
+    /// terminated frames.
+    ///
+    /// # Note
+    ///
+    /// The returned `NullDelimitedCodec` will not have an upper bound on the
+    /// length of a buffered frame. See the documentation for
+    /// [`new_with_limit`] for information on why this could be a potential
+    /// security risk.
+    ///
+    /// [`new_with_limit`]: #method.new_with_limit
+    pub fn new() -> NullDelimitedCodec {
+        NullDelimitedCodec {
+            delimited: Delimited::new(b'\0', usize::MAX),
+        }
+    }
+
+    /// Returns a `NullDelimitedCodec` with a maximum frame length limit.
+    ///
+    /// This behaves like [`LinesCodec::new_with_max_length`]: a frame which
+    /// exceeds the limit results in an `io::Error` of kind `InvalidData`, and
+    /// the rest of it is discarded up to the next `\0`, after which decoding
+    /// continues as normal.
+    ///
+    /// [`LinesCodec::new_with_max_length`]: struct.LinesCodec.html#method.new_with_max_length
+    pub fn new_with_limit(max_length: usize) -> NullDelimitedCodec {
+        NullDelimitedCodec {
+            delimited: Delimited::new(b'\0', max_length),
+        }
+    }
+
+    /// Returns the maximum frame length when decoding.
+    pub fn max_length(&self) -> usize {
+        self.delimited.max_length()
+    }
+}
+
+fn length_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "frame length limit exceeded")
+}
+
+impl Decoder for NullDelimitedCodec {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        let frame = try!(self.delimited.decode(buf, length_error));
+        Ok(frame.map(BytesMut::freeze))
+    }
+}
+
+impl Encoder for NullDelimitedCodec {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn encode(&mut self, data: Bytes, buf: &mut BytesMut) -> Result<(), io::Error> {
+        buf.reserve(data.len() + 1);
+        buf.put(data);
+        buf.put_u8(b'\0');
+        Ok(())
+    }
+}
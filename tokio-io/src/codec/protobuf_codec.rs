@@ -0,0 +1,137 @@
+use std::{error, fmt, io};
+use std::marker::PhantomData;
+
+use bytes::{Bytes, BytesMut};
+use codec::{Decoder, Encoder, VarIntCodec};
+use prost::{DecodeError, Message};
+
+/// A `Codec` implementation that frames Protocol Buffers messages of type
+/// `T`, each prefixed by its length as a varint.
+///
+/// This is the length-delimited format written by `writeDelimitedTo` in the
+/// Java implementation of Protocol Buffers, with the framing done by
+/// [`VarIntCodec`]. Messages are limited to 8MiB by default.
+///
+/// This codec is only available when the `prost` feature is enabled.
+///
+/// [`VarIntCodec`]: struct.VarIntCodec.html
+pub struct ProtobufCodec<T> {
+    frames: VarIntCodec,
+    _marker: PhantomData<fn(T) -> T>,
+}
+
+/// An error which occurred while encoding or decoding messages with a
+/// [`ProtobufCodec`].
+///
+/// [`ProtobufCodec`]: struct.ProtobufCodec.html
+#[derive(Debug)]
+pub enum ProtobufCodecError {
+    /// An I/O error, including malformed or too long length prefixes.
+    Io(io::Error),
+    /// A frame couldn't be decoded as a message.
+    DecodeError(DecodeError),
+}
+
+impl<T> ProtobufCodec<T>
+    where T: Message + Default,
+{
+    /// Returns a `ProtobufCodec` for encoding and decoding messages of type
+    /// `T`.
+    pub fn new() -> ProtobufCodec<T> {
+        ProtobufCodec {
+            frames: VarIntCodec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the largest encoded message this codec will accept from the
+    /// wire and write to the wire.
+    pub fn max_frame_length(&self) -> usize {
+        self.frames.max_frame_length()
+    }
+
+    /// Updates the max frame setting.
+    ///
+    /// Larger messages result in an `io::Error` of kind `InvalidData` when
+    /// decoding and `InvalidInput` when encoding.
+    pub fn set_max_frame_length(&mut self, val: usize) {
+        self.frames.set_max_frame_length(val);
+    }
+}
+
+impl<T> Decoder for ProtobufCodec<T>
+    where T: Message + Default,
+{
+    type Item = T;
+    type Error = ProtobufCodecError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<T>, ProtobufCodecError> {
+        match try!(self.frames.decode(buf)) {
+            Some(frame) => Ok(Some(try!(T::decode(&frame[..])))),
+            None => Ok(None),
+        }
+    }
+
+    fn max_buf_size(&self) -> usize {
+        self.frames.max_buf_size()
+    }
+}
+
+impl<T> Encoder for ProtobufCodec<T>
+    where T: Message + Default,
+{
+    type Item = T;
+    type Error = ProtobufCodecError;
+
+    fn encode(&mut self, item: T, buf: &mut BytesMut) -> Result<(), ProtobufCodecError> {
+        try!(self.frames.encode(Bytes::from(item.encode_to_vec()), buf));
+        Ok(())
+    }
+}
+
+impl<T> Clone for ProtobufCodec<T> {
+    fn clone(&self) -> ProtobufCodec<T> {
+        ProtobufCodec {
+            frames: self.frames.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for ProtobufCodec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProtobufCodec")
+            .field("frames", &self.frames)
+            .finish()
+    }
+}
+
+impl From<io::Error> for ProtobufCodecError {
+    fn from(e: io::Error) -> ProtobufCodecError {
+        ProtobufCodecError::Io(e)
+    }
+}
+
+impl From<DecodeError> for ProtobufCodecError {
+    fn from(e: DecodeError) -> ProtobufCodecError {
+        ProtobufCodecError::DecodeError(e)
+    }
+}
+
+impl fmt::Display for ProtobufCodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ProtobufCodecError::Io(ref e) => write!(f, "I/O error: {}", e),
+            ProtobufCodecError::DecodeError(ref e) => write!(f, "protobuf error: {}", e),
+        }
+    }
+}
+
+impl error::Error for ProtobufCodecError {
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            ProtobufCodecError::Io(ref e) => Some(e),
+            ProtobufCodecError::DecodeError(ref e) => Some(e),
+        }
+    }
+}
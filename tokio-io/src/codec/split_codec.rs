@@ -0,0 +1,101 @@
+use bytes::BytesMut;
+use codec::Decoder;
+
+/// A `Decoder` which decodes with one decoder up to a transition point, and
+/// with another one from then on, e.g. for a handshake followed by the
+/// actual protocol.
+///
+/// The first decoder is used until its `decode` returns `None` while the
+/// buffer is empty, meaning it has consumed all data it was given, after
+/// which the second decoder is used for good. The switch therefore relies on
+/// the peer waiting for the handshake to complete before sending data for
+/// the second decoder; data which arrives along with the last frame of the
+/// first phase is decoded by the first decoder.
+///
+/// Both decoders must yield the same type of item, see [`Decoder::map`] for
+/// converting between frame types.
+///
+/// [`Decoder::map`]: trait.Decoder.html#method.map
+#[derive(Debug)]
+pub struct SplitCodec<D1, D2> {
+    first: D1,
+    second: D2,
+    phase: Phase,
+}
+
+/// The decoder which a [`SplitCodec`] currently uses.
+///
+/// [`SplitCodec`]: struct.SplitCodec.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Phase {
+    /// The first decoder is used.
+    First,
+    /// The second decoder is used.
+    Second,
+}
+
+impl<D1, D2> SplitCodec<D1, D2> {
+    /// Creates a `SplitCodec` decoding with `first` until its transition to
+    /// `second`.
+    pub fn new(first: D1, second: D2) -> SplitCodec<D1, D2> {
+        SplitCodec {
+            first: first,
+            second: second,
+            phase: Phase::First,
+        }
+    }
+
+    /// Returns which decoder is currently used.
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// Returns a reference to the first decoder.
+    pub fn first(&self) -> &D1 {
+        &self.first
+    }
+
+    /// Returns a reference to the second decoder.
+    pub fn second(&self) -> &D2 {
+        &self.second
+    }
+
+    /// Consumes the `SplitCodec`, returning both decoders.
+    pub fn into_inner(self) -> (D1, D2) {
+        (self.first, self.second)
+    }
+}
+
+impl<D1, D2> Decoder for SplitCodec<D1, D2>
+    where D1: Decoder,
+          D2: Decoder<Item = D1::Item>,
+          D1::Error: From<D2::Error>,
+{
+    type Item = D1::Item;
+    type Error = D1::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<D1::Item>, D1::Error> {
+        if self.phase == Phase::First {
+            match try!(self.first.decode(buf)) {
+                Some(item) => return Ok(Some(item)),
+                None if buf.is_empty() => self.phase = Phase::Second,
+                None => return Ok(None),
+            }
+        }
+        Ok(try!(self.second.decode(buf)))
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<D1::Item>, D1::Error> {
+        match self.phase {
+            Phase::First => self.first.decode_eof(buf),
+            Phase::Second => Ok(try!(self.second.decode_eof(buf))),
+        }
+    }
+
+    fn max_buf_size(&self) -> usize {
+        match self.phase {
+            Phase::First => self.first.max_buf_size(),
+            Phase::Second => self.second.max_buf_size(),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use codec::{Encoder, Decoder};
+use std::io;
+
+// A `u64` takes at most 10 bytes when varint encoded.
+const MAX_VARINT_LEN: usize = 10;
+
+/// A `Codec` for frames prefixed by their length as a varint, as used by
+/// Protocol Buffers and gRPC.
+///
+/// A varint stores an integer in groups of 7 bits, least significant group
+/// first, with the high bit of every byte set if more bytes follow. With
+/// [`new_zigzag`] the length is additionally zig-zag encoded, like the
+/// `sint` types of Protocol Buffers.
+///
+/// Decoded frames are yielded as `BytesMut` with the length prefix stripped,
+/// and frames are encoded from `Bytes`, prepending the length prefix. A
+/// malformed prefix or a frame longer than the [max frame length] results in
+/// an `io::Error` of kind `InvalidData`.
+///
+/// [`new_zigzag`]: #method.new_zigzag
+/// [max frame length]: #method.max_frame_length
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct VarIntCodec {
+    zigzag: bool,
+    max_frame_len: usize,
+    // Length of the frame being decoded, once its prefix has been read.
+    frame_len: Option<usize>,
+}
+
+impl VarIntCodec {
+    /// Returns a `VarIntCodec` using standard varint encoding for the length
+    /// prefix.
+    ///
+    /// The max frame length defaults to 8MiB.
+    pub fn new() -> VarIntCodec {
+        VarIntCodec {
+            zigzag: false,
+            max_frame_len: 8 * 1_024 * 1_024,
+            frame_len: None,
+        }
+    }
+
+    /// Returns a `VarIntCodec` using zig-zag varint encoding for the length
+    /// prefix.
+    ///
+    /// The max frame length defaults to 8MiB.
+    pub fn new_zigzag() -> VarIntCodec {
+        VarIntCodec {
+            zigzag: true,
+            ..VarIntCodec::new()
+        }
+    }
+
+    /// Returns whether the length prefix is zig-zag encoded.
+    pub fn is_zigzag(&self) -> bool {
+        self.zigzag
+    }
+
+    /// Returns the current max frame setting.
+    ///
+    /// This is the largest size this codec will accept from the wire and
+    /// write to the wire. Larger frames will be rejected.
+    pub fn max_frame_length(&self) -> usize {
+        self.max_frame_len
+    }
+
+    /// Updates the max frame setting.
+    ///
+    /// The change takes effect the next time a length prefix is decoded or a
+    /// frame is encoded.
+    pub fn set_max_frame_length(&mut self, val: usize) {
+        self.max_frame_len = val;
+    }
+
+    fn decode_head(&self, src: &mut BytesMut) -> io::Result<Option<usize>> {
+        let mut value = 0u64;
+        let mut head_len = None;
+        for (i, &b) in src.iter().take(MAX_VARINT_LEN).enumerate() {
+            let bits = (b & 0x7f) as u64;
+            // The tenth byte only has room for the top bit of a `u64`.
+            if i == MAX_VARINT_LEN - 1 && bits > 1 {
+                return Err(invalid_data("varint length prefix overflows u64"));
+            }
+            value |= bits << (7 * i);
+            if b & 0x80 == 0 {
+                head_len = Some(i + 1);
+                break;
+            }
+        }
+
+        let head_len = match head_len {
+            Some(n) => n,
+            None if src.len() >= MAX_VARINT_LEN => {
+                return Err(invalid_data("varint length prefix is too long"));
+            }
+            None => return Ok(None),
+        };
+
+        if self.zigzag {
+            if value & 1 == 1 {
+                return Err(invalid_data("negative frame length"));
+            }
+            value >>= 1;
+        }
+        if value > self.max_frame_len as u64 {
+            return Err(invalid_data("frame length limit exceeded"));
+        }
+
+        let _ = src.split_to(head_len);
+        Ok(Some(value as usize))
+    }
+
+    fn encode_head(&self, len: usize, dst: &mut BytesMut) {
+        let mut value = len as u64;
+        if self.zigzag {
+            value <<= 1;
+        }
+        while value >= 0x80 {
+            dst.put_u8(value as u8 | 0x80);
+            value >>= 7;
+        }
+        dst.put_u8(value as u8);
+    }
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+impl Decoder for VarIntCodec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<BytesMut>, io::Error> {
+        let n = match self.frame_len {
+            Some(n) => n,
+            None => match try!(self.decode_head(src)) {
+                Some(n) => {
+                    self.frame_len = Some(n);
+                    src.reserve(n);
+                    n
+                }
+                None => return Ok(None),
+            },
+        };
+
+        if src.len() < n {
+            return Ok(None);
+        }
+        self.frame_len = None;
+        Ok(Some(src.split_to(n)))
+    }
+
+    fn max_buf_size(&self) -> usize {
+        self.max_frame_len.saturating_add(MAX_VARINT_LEN)
+    }
+}
+
+impl Encoder for VarIntCodec {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn encode(&mut self, data: Bytes, dst: &mut BytesMut) -> Result<(), io::Error> {
+        if data.len() > self.max_frame_len {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      "frame length limit exceeded"));
+        }
+        dst.reserve(MAX_VARINT_LEN + data.len());
+        self.encode_head(data.len(), dst);
+        dst.put(data);
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use bytes::BytesMut;
+use codec::{Decoder, Encoder};
+
+/// An `Encoder` which transforms items with a closure before encoding them
+/// with another encoder.
+///
+/// Created by the [`Encoder::with`] method.
+///
+/// [`Encoder::with`]: trait.Encoder.html#method.with
+pub struct WithEncoder<E, F, U> {
+    encoder: E,
+    f: F,
+    _marker: PhantomData<fn(U)>,
+}
+
+pub fn new<E, F, U>(encoder: E, f: F) -> WithEncoder<E, F, U> {
+    WithEncoder {
+        encoder: encoder,
+        f: f,
+        _marker: PhantomData,
+    }
+}
+
+impl<E, F, U> WithEncoder<E, F, U> {
+    /// Returns a reference to the underlying encoder.
+    pub fn get_ref(&self) -> &E {
+        &self.encoder
+    }
+
+    /// Returns a mutable reference to the underlying encoder.
+    pub fn get_mut(&mut self) -> &mut E {
+        &mut self.encoder
+    }
+
+    /// Consumes the `WithEncoder`, returning the underlying encoder.
+    pub fn into_inner(self) -> E {
+        self.encoder
+    }
+}
+
+impl<E, F, U> Encoder for WithEncoder<E, F, U>
+    where E: Encoder,
+          F: FnMut(U) -> E::Item,
+{
+    type Item = U;
+    type Error = E::Error;
+
+    fn encode(&mut self, item: U, dst: &mut BytesMut) -> Result<(), E::Error> {
+        let item = (self.f)(item);
+        self.encoder.encode(item, dst)
+    }
+}
+
+impl<E: Decoder, F, U> Decoder for WithEncoder<E, F, U> {
+    type Item = E::Item;
+    type Error = E::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<E::Item>, E::Error> {
+        self.encoder.decode(buf)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<E::Item>, E::Error> {
+        self.encoder.decode_eof(buf)
+    }
+
+    fn max_buf_size(&self) -> usize {
+        self.encoder.max_buf_size()
+    }
+}
+
+impl<E: Clone, F: Clone, U> Clone for WithEncoder<E, F, U> {
+    fn clone(&self) -> WithEncoder<E, F, U> {
+        WithEncoder {
+            encoder: self.encoder.clone(),
+            f: self.f.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E: fmt::Debug, F, U> fmt::Debug for WithEncoder<E, F, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WithEncoder")
+            .field("encoder", &self.encoder)
+            .finish()
+    }
+}
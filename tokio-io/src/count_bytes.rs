@@ -0,0 +1,101 @@
+use std::io::{self, BufRead, Read, Write};
+
+use futures::Poll;
+
+use {AsyncRead, AsyncWrite};
+
+/// A reader which counts the bytes read through it.
+///
+/// Created by the [`count_bytes`] function.
+///
+/// [`count_bytes`]: fn.count_bytes.html
+#[derive(Debug)]
+pub struct CountBytes<R> {
+    inner: R,
+    bytes_read: u64,
+}
+
+/// Wraps a reader and counts the bytes read from it.
+///
+/// All reads are passed through to `inner`. Bytes are counted when they are
+/// returned by `read` or, if the reader is also a `BufRead`, when they are
+/// consumed, so data which is only looked at through `fill_buf` isn't
+/// counted. The total is available through [`CountBytes::bytes_read`], which
+/// is useful for progress reporting and metrics. Writes are passed through
+/// without being counted.
+///
+/// [`CountBytes::bytes_read`]: struct.CountBytes.html#method.bytes_read
+pub fn count_bytes<R>(inner: R) -> CountBytes<R>
+    where R: AsyncRead,
+{
+    CountBytes {
+        inner: inner,
+        bytes_read: 0,
+    }
+}
+
+impl<R> CountBytes<R> {
+    /// Returns the number of bytes read so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Note that bytes read directly from the underlying reader aren't
+    /// counted.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the `CountBytes`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> Read for CountBytes<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = try!(self.inner.read(buf));
+        self.bytes_read += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: AsyncRead + BufRead> BufRead for CountBytes<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.bytes_read += amt as u64;
+        self.inner.consume(amt)
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for CountBytes<R> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<R: Write> Write for CountBytes<R> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<R: AsyncWrite> AsyncWrite for CountBytes<R> {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.inner.shutdown()
+    }
+}
@@ -0,0 +1,100 @@
+use std::io::{self, BufRead, Read, Write};
+
+use futures::Poll;
+
+use {AsyncRead, AsyncWrite};
+
+/// A writer which counts the bytes written through it.
+///
+/// Created by the [`count_write`] function.
+///
+/// [`count_write`]: fn.count_write.html
+#[derive(Debug)]
+pub struct CountWrite<W> {
+    inner: W,
+    bytes_written: u64,
+}
+
+/// Wraps a writer and counts the bytes written to it.
+///
+/// This is the counterpart of [`count_bytes`]. All writes are passed through
+/// to `inner`, and only the bytes which `inner` accepted are counted, so
+/// after flushing [`CountWrite::bytes_written`] is the amount of data which
+/// actually made it to the writer, e.g. to report the length of a response
+/// of unknown size. Reads are passed through without being counted.
+///
+/// [`count_bytes`]: fn.count_bytes.html
+/// [`CountWrite::bytes_written`]: struct.CountWrite.html#method.bytes_written
+pub fn count_write<W>(inner: W) -> CountWrite<W>
+    where W: AsyncWrite,
+{
+    CountWrite {
+        inner: inner,
+        bytes_written: 0,
+    }
+}
+
+impl<W> CountWrite<W> {
+    /// Returns the number of bytes written so far.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Note that bytes written directly to the underlying writer aren't
+    /// counted.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes the `CountWrite`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> Write for CountWrite<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = try!(self.inner.write(buf));
+        self.bytes_written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for CountWrite<W> {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.inner.shutdown()
+    }
+}
+
+impl<W: Read> Read for CountWrite<W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<W: BufRead> BufRead for CountWrite<W> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+impl<W: AsyncRead> AsyncRead for CountWrite<W> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
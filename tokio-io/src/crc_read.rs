@@ -0,0 +1,180 @@
+use std::{cmp, fmt};
+use std::io::{self, BufRead, Read};
+
+use bytes::{BigEndian, ByteOrder};
+use crc32fast::Hasher;
+use futures::{Future, Poll};
+
+use AsyncRead;
+
+const CRC_LEN: usize = 4;
+
+/// A buffered reader for data followed by a CRC32 checksum of it.
+///
+/// The last 4 bytes of the underlying reader are taken to be the CRC32 of
+/// all preceding bytes, stored as a big-endian `u32`. Reads through
+/// `CrcRead` only return the data preceding the checksum, so it can be
+/// parsed without knowing its length up front, while a running CRC32 is
+/// computed of the data consumed. Once done, [`verify`] compares it against
+/// the checksum.
+///
+/// Created by the [`crc_read`] function. This type is only available when
+/// the `crc32` feature is enabled.
+///
+/// [`verify`]: #method.verify
+/// [`crc_read`]: fn.crc_read.html
+pub struct CrcRead<R> {
+    inner: R,
+    hasher: Hasher,
+    // Data read from `inner`; the last `CRC_LEN` bytes are held back since
+    // they may turn out to be the checksum.
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+/// A future which checks the checksum at the end of a [`CrcRead`].
+///
+/// Created by the [`CrcRead::verify`] method.
+///
+/// [`CrcRead`]: struct.CrcRead.html
+/// [`CrcRead::verify`]: struct.CrcRead.html#method.verify
+#[derive(Debug)]
+pub struct VerifyCrc<R> {
+    state: Option<CrcRead<R>>,
+}
+
+/// Creates a new [`CrcRead`] reading data followed by its CRC32 from
+/// `reader`.
+///
+/// [`CrcRead`]: struct.CrcRead.html
+pub fn crc_read<R>(reader: R) -> CrcRead<R>
+    where R: AsyncRead + BufRead,
+{
+    CrcRead {
+        inner: reader,
+        hasher: Hasher::new(),
+        buf: Vec::new(),
+        pos: 0,
+    }
+}
+
+impl<R> CrcRead<R>
+    where R: AsyncRead + BufRead,
+{
+    /// Returns a future which reads the rest of the data and the checksum,
+    /// and checks that the checksum matches the data.
+    ///
+    /// Data which hasn't been consumed yet is skipped over, but still
+    /// included in the CRC32. The future resolves to the underlying reader
+    /// and the CRC32 of the data. If the checksum doesn't match, the future
+    /// resolves to an error of kind `InvalidData`, and if the reader ends
+    /// before a complete checksum, to an error of kind `UnexpectedEof`.
+    pub fn verify(self) -> VerifyCrc<R> {
+        VerifyCrc { state: Some(self) }
+    }
+}
+
+impl<R> CrcRead<R> {
+    /// Returns the CRC32 of the data consumed so far.
+    pub fn crc(&self) -> u32 {
+        self.hasher.clone().finalize()
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Note that reading directly from the underlying reader skips over the
+    /// data buffered by the `CrcRead`, which breaks the checksum.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+}
+
+impl<R: AsyncRead + BufRead> Read for CrcRead<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = {
+            let mut rem = try!(self.fill_buf());
+            try!(rem.read(buf))
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: AsyncRead + BufRead> BufRead for CrcRead<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.buf.len() - self.pos <= CRC_LEN {
+            let n = {
+                let available = try!(self.inner.fill_buf());
+                if available.is_empty() {
+                    break;
+                }
+                self.buf.drain(..self.pos);
+                self.pos = 0;
+                self.buf.extend_from_slice(available);
+                available.len()
+            };
+            self.inner.consume(n);
+        }
+        let end = cmp::max(self.buf.len(), self.pos + CRC_LEN) - CRC_LEN;
+        Ok(&self.buf[self.pos..end])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let end = cmp::max(self.buf.len(), self.pos + CRC_LEN) - CRC_LEN;
+        let amt = cmp::min(amt, end - self.pos);
+        self.hasher.update(&self.buf[self.pos..self.pos + amt]);
+        self.pos += amt;
+    }
+}
+
+impl<R: AsyncRead + BufRead> AsyncRead for CrcRead<R> {
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for CrcRead<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CrcRead")
+            .field("inner", &self.inner)
+            .field("crc", &self.crc())
+            .field("buffered", &(self.buf.len() - self.pos))
+            .finish()
+    }
+}
+
+impl<R> Future for VerifyCrc<R>
+    where R: AsyncRead + BufRead,
+{
+    type Item = (R, u32);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(R, u32), io::Error> {
+        {
+            let rd = self.state.as_mut().expect("poll VerifyCrc after it's done");
+            loop {
+                let n = try_nb!(rd.fill_buf()).len();
+                if n == 0 {
+                    break;
+                }
+                rd.consume(n);
+            }
+        }
+
+        let rd = self.state.take().unwrap();
+        let trailer = &rd.buf[rd.pos..];
+        if trailer.len() < CRC_LEN {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "missing CRC32"));
+        }
+        let crc = rd.crc();
+        if BigEndian::read_u32(trailer) != crc {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "CRC32 mismatch"));
+        }
+        Ok((rd.inner, crc).into())
+    }
+}
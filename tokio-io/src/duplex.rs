@@ -0,0 +1,98 @@
+use std::io::{self, BufRead, Read, Write};
+
+use futures::Poll;
+
+use {AsyncRead, AsyncWrite};
+use pipe::{pipe, PipeReader, PipeWriter};
+
+/// One end of an in-memory bidirectional stream.
+///
+/// Created by the [`duplex`] function.
+///
+/// [`duplex`]: fn.duplex.html
+#[derive(Debug)]
+pub struct DuplexStream {
+    read: PipeReader,
+    write: PipeWriter,
+}
+
+/// Creates a pair of connected in-memory streams.
+///
+/// Data written to one of the streams can be read from the other one, in
+/// both directions, so the pair behaves like a connected socket. Each
+/// direction is a [`pipe`] with a capacity of `max_buf_size` bytes, so
+/// writes block once that many bytes are waiting to be read from the other
+/// end.
+///
+/// Shutting down the write half of a stream, or dropping it, signals EOF to
+/// the other end once all data written before has been read. Writing to a
+/// stream whose other end has been dropped fails with an error of kind
+/// `BrokenPipe`.
+///
+/// This is useful for testing protocol implementations without sockets.
+/// Like any other asynchronous I/O object the streams must be used from
+/// within a task.
+///
+/// [`pipe`]: fn.pipe.html
+///
+/// # Panics
+///
+/// Panics if `max_buf_size` is 0.
+pub fn duplex(max_buf_size: usize) -> (DuplexStream, DuplexStream) {
+    let (write_a, read_b) = pipe(max_buf_size);
+    let (write_b, read_a) = pipe(max_buf_size);
+    let a = DuplexStream {
+        read: read_a,
+        write: write_a,
+    };
+    let b = DuplexStream {
+        read: read_b,
+        write: write_b,
+    };
+    (a, b)
+}
+
+impl DuplexStream {
+    /// Returns the number of bytes which can be read without blocking.
+    pub fn buffered_bytes(&self) -> usize {
+        self.read.buffered_bytes()
+    }
+}
+
+impl Read for DuplexStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.read.read(buf)
+    }
+}
+
+impl BufRead for DuplexStream {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.read.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.read.consume(amt)
+    }
+}
+
+impl AsyncRead for DuplexStream {
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+}
+
+impl Write for DuplexStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write.flush()
+    }
+}
+
+impl AsyncWrite for DuplexStream {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.write.shutdown()
+    }
+}
@@ -0,0 +1,66 @@
+use std::io;
+
+use futures::Poll;
+
+use {AsyncRead, AsyncWrite};
+
+/// A reader and writer which is always at EOF and discards all data written
+/// to it.
+///
+/// Unlike `std::io::Empty` this also implements `Write`, so the same value
+/// can stand in for both ends of a connection in tests.
+///
+/// Created by the [`empty`] function.
+///
+/// [`empty`]: fn.empty.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Empty {
+    _priv: (),
+}
+
+/// Creates a value which is always at EOF for reads and accepts and discards
+/// all writes.
+///
+/// All reads from it return `Ok(0)` and `fill_buf` returns an empty slice.
+/// All writes succeed immediately, reporting every byte as written, and
+/// flushing and shutting down complete immediately as well.
+pub fn empty() -> Empty {
+    Empty { _priv: () }
+}
+
+impl io::Read for Empty {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Ok(0)
+    }
+}
+
+impl io::BufRead for Empty {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(&[])
+    }
+
+    fn consume(&mut self, _amt: usize) {
+    }
+}
+
+impl AsyncRead for Empty {
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+}
+
+impl io::Write for Empty {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for Empty {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        Ok(().into())
+    }
+}
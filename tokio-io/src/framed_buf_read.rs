@@ -0,0 +1,158 @@
+use std::fmt;
+use std::io::BufRead;
+
+use AsyncRead;
+use codec::Decoder;
+use framed_read::buffer_overflow;
+
+use futures::{Async, Poll, Stream};
+use bytes::BytesMut;
+
+/// A `Stream` of messages decoded from a buffered reader.
+///
+/// This is similar to [`FramedRead`], but instead of reading into a large
+/// buffer of its own it takes data out of the reader's buffer through
+/// `BufRead::fill_buf`. Whenever the decoder needs more bytes to produce a
+/// frame, everything `fill_buf` returns is copied into the decode buffer and
+/// consumed from the reader, even if the decoder ends up using only part of
+/// it. The decode buffer therefore holds at most one `fill_buf` worth of
+/// data on top of a partial frame.
+///
+/// [`FramedRead`]: struct.FramedRead.html
+pub struct FramedBufRead<T, D> {
+    inner: T,
+    decoder: D,
+    eof: bool,
+    is_readable: bool,
+    buffer: BytesMut,
+}
+
+impl<T, D> FramedBufRead<T, D>
+    where T: AsyncRead + BufRead,
+          D: Decoder,
+{
+    /// Creates a new `FramedBufRead` with the given `decoder`.
+    pub fn new(inner: T, decoder: D) -> FramedBufRead<T, D> {
+        FramedBufRead {
+            inner: inner,
+            decoder: decoder,
+            eof: false,
+            is_readable: false,
+            buffer: BytesMut::new(),
+        }
+    }
+}
+
+impl<T, D> FramedBufRead<T, D> {
+    /// Returns a reference to the underlying I/O stream wrapped by
+    /// `FramedBufRead`.
+    ///
+    /// Note that care should be taken to not tamper with the underlying stream
+    /// of data coming in as it may corrupt the stream of frames otherwise
+    /// being worked with.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying I/O stream wrapped by
+    /// `FramedBufRead`.
+    ///
+    /// Note that care should be taken to not tamper with the underlying stream
+    /// of data coming in as it may corrupt the stream of frames otherwise
+    /// being worked with.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes the `FramedBufRead`, returning its underlying I/O stream.
+    ///
+    /// Note that any bytes which were taken from the stream but not decoded
+    /// yet are lost. Use [`into_parts`] to recover them as well.
+    ///
+    /// [`into_parts`]: #method.into_parts
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Consumes the `FramedBufRead`, returning its underlying I/O stream and
+    /// the bytes which were taken from it but not decoded yet.
+    ///
+    /// Any data still buffered inside the I/O stream itself remains there.
+    pub fn into_parts(self) -> (T, BytesMut) {
+        (self.inner, self.buffer)
+    }
+
+    /// Returns a reference to the underlying decoder.
+    pub fn decoder(&self) -> &D {
+        &self.decoder
+    }
+
+    /// Returns a mutable reference to the underlying decoder.
+    pub fn decoder_mut(&mut self) -> &mut D {
+        &mut self.decoder
+    }
+}
+
+impl<T, D> Stream for FramedBufRead<T, D>
+    where T: AsyncRead + BufRead,
+          D: Decoder,
+{
+    type Item = D::Item;
+    type Error = D::Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        loop {
+            // Repeatedly call `decode` or `decode_eof` as long as it is
+            // "readable", following the same rules as `FramedRead`.
+            if self.is_readable {
+                if self.eof {
+                    let frame = try!(self.decoder.decode_eof(&mut self.buffer));
+                    return Ok(Async::Ready(frame));
+                }
+
+                trace!("attempting to decode a frame");
+
+                if let Some(frame) = try!(self.decoder.decode(&mut self.buffer)) {
+                    trace!("frame decoded from buffer");
+                    return Ok(Async::Ready(Some(frame)));
+                }
+
+                self.is_readable = false;
+
+                if self.buffer.len() > self.decoder.max_buf_size() {
+                    return Err(buffer_overflow().into());
+                }
+            }
+
+            assert!(!self.eof);
+
+            // Otherwise, take whatever the reader has buffered and try again.
+            let n = {
+                let available = try_nb!(self.inner.fill_buf());
+                self.buffer.extend_from_slice(available);
+                available.len()
+            };
+            if n == 0 {
+                self.eof = true;
+            }
+            self.inner.consume(n);
+
+            self.is_readable = true;
+        }
+    }
+}
+
+impl<T, D> fmt::Debug for FramedBufRead<T, D>
+    where T: fmt::Debug,
+          D: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FramedBufRead")
+            .field("inner", &self.inner)
+            .field("decoder", &self.decoder)
+            .field("eof", &self.eof)
+            .field("is_readable", &self.is_readable)
+            .field("buffer", &self.buffer)
+            .finish()
+    }
+}
@@ -1,13 +1,17 @@
-use std::fmt;
+use std::{fmt, io};
 
 use AsyncRead;
-use codec::Decoder;
+use codec::{Decoder, FilterMapDecoder, MapDecoder};
 use framed::Fuse;
 
 use futures::{Async, Poll, Stream, Sink, StartSend};
 use bytes::BytesMut;
 
 /// A `Stream` of messages decoded from an `AsyncRead`.
+///
+/// The stream yields the decoder's `Item`s and fails with the decoder's own
+/// `Error` type, so no mapping is needed to get at a codec specific error.
+/// I/O errors of the underlying reader are converted into it with `From`.
 pub struct FramedRead<T, D> {
     inner: FramedRead2<Fuse<T, D>>,
 }
@@ -33,6 +37,18 @@ impl<T, D> FramedRead<T, D>
             inner: framed_read2(Fuse(inner, decoder)),
         }
     }
+
+    /// Creates a new `FramedRead` with the given `decoder` and a buffer of
+    /// `capacity` bytes.
+    ///
+    /// By default the read buffer starts out with 8KiB and grows as needed.
+    /// If frames are known to be large, pre-allocating a buffer big enough
+    /// to hold an entire frame avoids reallocating it while reading.
+    pub fn with_capacity(inner: T, decoder: D, capacity: usize) -> FramedRead<T, D> {
+        FramedRead {
+            inner: framed_read2_with_capacity(Fuse(inner, decoder), capacity),
+        }
+    }
 }
 
 impl<T, D> FramedRead<T, D> {
@@ -65,6 +81,16 @@ impl<T, D> FramedRead<T, D> {
         self.inner.inner.0
     }
 
+    /// Consumes the `FramedRead`, returning its underlying I/O stream and the
+    /// buffer with data which has been read but not decoded yet.
+    ///
+    /// This allows switching from frames to raw bytes, e.g. after a protocol
+    /// upgrade, without losing data the decoder didn't consume.
+    pub fn into_parts(self) -> (T, BytesMut) {
+        let (inner, buffer) = self.inner.into_parts();
+        (inner.0, buffer)
+    }
+
     /// Returns a reference to the underlying decoder.
     pub fn decoder(&self) -> &D {
         &self.inner.inner.1
@@ -74,6 +100,52 @@ impl<T, D> FramedRead<T, D> {
     pub fn decoder_mut(&mut self) -> &mut D {
         &mut self.inner.inner.1
     }
+
+    /// Returns the number of bytes the read buffer can hold without
+    /// reallocating.
+    pub fn capacity(&self) -> usize {
+        self.inner.buffer.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more bytes in the read
+    /// buffer, on top of the data which is currently buffered.
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.buffer.reserve(additional)
+    }
+}
+
+impl<T, D: Decoder> FramedRead<T, D> {
+    /// Transforms every decoded frame with `f` by wrapping the decoder.
+    ///
+    /// This is the same as [`Decoder::map`] on the decoder, but keeps the
+    /// data which has already been read, so it can be used on a `FramedRead`
+    /// which is in use. Errors are passed through unchanged. Unlike
+    /// `Stream::map` the result is still a `FramedRead`, so the decoder stays
+    /// accessible.
+    ///
+    /// [`Decoder::map`]: trait.Decoder.html#method.map
+    pub fn map_decoder<F, U>(self, f: F) -> FramedRead<T, MapDecoder<D, F>>
+        where F: FnMut(D::Item) -> U,
+    {
+        FramedRead {
+            inner: self.inner.map_inner(|Fuse(io, decoder)| Fuse(io, decoder.map(f))),
+        }
+    }
+
+    /// Transforms every decoded frame with `f`, skipping the frames for which
+    /// it returns `None`.
+    ///
+    /// This is the same as [`Decoder::filter_map`] on the decoder, but keeps
+    /// the data which has already been read.
+    ///
+    /// [`Decoder::filter_map`]: trait.Decoder.html#method.filter_map
+    pub fn filter_map_decoder<F, U>(self, f: F) -> FramedRead<T, FilterMapDecoder<D, F>>
+        where F: FnMut(D::Item) -> Option<U>,
+    {
+        FramedRead {
+            inner: self.inner.map_inner(|Fuse(io, decoder)| Fuse(io, decoder.filter_map(f))),
+        }
+    }
 }
 
 impl<T, D> Stream for FramedRead<T, D>
@@ -127,12 +199,20 @@ impl<T, D> fmt::Debug for FramedRead<T, D>
 
 // ===== impl FramedRead2 =====
 
+pub fn buffer_overflow() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "decoder buffer overflow")
+}
+
 pub fn framed_read2<T>(inner: T) -> FramedRead2<T> {
+    framed_read2_with_capacity(inner, INITIAL_CAPACITY)
+}
+
+pub fn framed_read2_with_capacity<T>(inner: T, capacity: usize) -> FramedRead2<T> {
     FramedRead2 {
         inner: inner,
         eof: false,
         is_readable: false,
-        buffer: BytesMut::with_capacity(INITIAL_CAPACITY),
+        buffer: BytesMut::with_capacity(capacity),
     }
 }
 
@@ -162,6 +242,15 @@ impl<T> FramedRead2<T> {
         (self.inner, self.buffer)
     }
 
+    pub fn map_inner<U, F: FnOnce(T) -> U>(self, f: F) -> FramedRead2<U> {
+        FramedRead2 {
+            inner: f(self.inner),
+            eof: self.eof,
+            is_readable: self.is_readable,
+            buffer: self.buffer,
+        }
+    }
+
     pub fn get_mut(&mut self) -> &mut T {
         &mut self.inner
     }
@@ -194,6 +283,10 @@ impl<T> Stream for FramedRead2<T>
                 }
 
                 self.is_readable = false;
+
+                if self.buffer.len() > self.inner.max_buf_size() {
+                    return Err(buffer_overflow().into());
+                }
             }
 
             assert!(!self.eof);
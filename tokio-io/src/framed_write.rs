@@ -1,8 +1,8 @@
 use std::io::{self, Read};
-use std::fmt;
+use std::{cmp, fmt};
 
 use {AsyncRead, AsyncWrite};
-use codec::{Decoder, Encoder};
+use codec::{Decoder, Encoder, WithEncoder};
 use framed::Fuse;
 
 use futures::{Async, AsyncSink, Poll, Stream, Sink, StartSend};
@@ -16,6 +16,11 @@ pub struct FramedWrite<T, E> {
 pub struct FramedWrite2<T> {
     inner: T,
     buffer: BytesMut,
+    high_water_mark: usize,
+    low_water_mark: usize,
+    // Set once the high-water mark was hit, until the buffer drained below
+    // the low-water mark.
+    paused: bool,
 }
 
 const INITIAL_CAPACITY: usize = 8 * 1024;
@@ -63,6 +68,53 @@ impl<T, E> FramedWrite<T, E> {
         self.inner.inner.0
     }
 
+    /// Consumes the `FramedWrite`, returning its underlying I/O stream and the
+    /// buffer with encoded frames which haven't been written out yet.
+    ///
+    /// The buffered bytes can then be written to the I/O stream directly,
+    /// e.g. before switching to a different protocol.
+    pub fn into_parts(self) -> (T, BytesMut) {
+        let (inner, buffer) = self.inner.into_parts();
+        (inner.0, buffer)
+    }
+
+    /// Returns the current high-water mark.
+    ///
+    /// Once this many bytes are buffered, `start_send` tries to flush the
+    /// buffer before encoding another frame, and rejects frames until the
+    /// buffer has been drained below the [low-water mark]. The default is
+    /// 8KiB.
+    ///
+    /// [low-water mark]: #method.low_water_mark
+    pub fn high_water_mark(&self) -> usize {
+        self.inner.high_water_mark
+    }
+
+    /// Updates the high-water mark.
+    ///
+    /// A single frame is always accepted into an empty buffer, so the buffer
+    /// grows beyond the mark as needed to fit large frames.
+    pub fn set_high_water_mark(&mut self, val: usize) {
+        self.inner.high_water_mark = val;
+    }
+
+    /// Returns the current low-water mark.
+    ///
+    /// Once the high-water mark has been hit, `start_send` keeps flushing the
+    /// buffer and rejecting frames until fewer than this many bytes are
+    /// buffered, or the buffer is empty. A mark below the high-water mark avoids switching between
+    /// accepting and rejecting frames with every partial write when the
+    /// writer is slower than the producer. The default is 8KiB, and the mark
+    /// never exceeds the high-water mark.
+    pub fn low_water_mark(&self) -> usize {
+        self.inner.low_water_mark()
+    }
+
+    /// Updates the low-water mark.
+    pub fn set_low_water_mark(&mut self, val: usize) {
+        self.inner.low_water_mark = val;
+    }
+
     /// Returns a reference to the underlying decoder.
     pub fn encoder(&self) -> &E {
         &self.inner.inner.1
@@ -74,6 +126,25 @@ impl<T, E> FramedWrite<T, E> {
     }
 }
 
+impl<T, E: Encoder> FramedWrite<T, E> {
+    /// Transforms every item with `f` before encoding it by wrapping the
+    /// encoder.
+    ///
+    /// This is the same as [`Encoder::with`] on the encoder, but keeps the
+    /// frames which are buffered and the water marks, so it can be used on a
+    /// `FramedWrite` which is in use. Unlike `Sink::with` the closure returns
+    /// the item to encode rather than a future.
+    ///
+    /// [`Encoder::with`]: trait.Encoder.html#method.with
+    pub fn with_encoder<F, U>(self, f: F) -> FramedWrite<T, WithEncoder<E, F, U>>
+        where F: FnMut(U) -> E::Item,
+    {
+        FramedWrite {
+            inner: self.inner.map_inner(|Fuse(io, encoder)| Fuse(io, encoder.with(f))),
+        }
+    }
+}
+
 impl<T, E> Sink for FramedWrite<T, E>
     where T: AsyncWrite,
           E: Encoder,
@@ -114,6 +185,8 @@ impl<T, U> fmt::Debug for FramedWrite<T, U>
          .field("inner", &self.inner.get_ref().0)
          .field("encoder", &self.inner.get_ref().1)
          .field("buffer", &self.inner.buffer)
+         .field("high_water_mark", &self.inner.high_water_mark)
+         .field("low_water_mark", &self.inner.low_water_mark())
          .finish()
     }
 }
@@ -124,6 +197,9 @@ pub fn framed_write2<T>(inner: T) -> FramedWrite2<T> {
     FramedWrite2 {
         inner: inner,
         buffer: BytesMut::with_capacity(INITIAL_CAPACITY),
+        high_water_mark: BACKPRESSURE_BOUNDARY,
+        low_water_mark: BACKPRESSURE_BOUNDARY,
+        paused: false,
     }
 }
 
@@ -135,6 +211,9 @@ pub fn framed_write2_with_buffer<T>(inner: T, mut buf: BytesMut) -> FramedWrite2
     FramedWrite2 {
         inner: inner,
         buffer: buf,
+        high_water_mark: BACKPRESSURE_BOUNDARY,
+        low_water_mark: BACKPRESSURE_BOUNDARY,
+        paused: false,
     }
 }
 
@@ -151,9 +230,23 @@ impl<T> FramedWrite2<T> {
         (self.inner, self.buffer)
     }
 
+    pub fn map_inner<U, F: FnOnce(T) -> U>(self, f: F) -> FramedWrite2<U> {
+        FramedWrite2 {
+            inner: f(self.inner),
+            buffer: self.buffer,
+            high_water_mark: self.high_water_mark,
+            low_water_mark: self.low_water_mark,
+            paused: self.paused,
+        }
+    }
+
     pub fn get_mut(&mut self) -> &mut T {
         &mut self.inner
     }
+
+    fn low_water_mark(&self) -> usize {
+        cmp::min(self.low_water_mark, self.high_water_mark)
+    }
 }
 
 impl<T> Sink for FramedWrite2<T>
@@ -163,12 +256,15 @@ impl<T> Sink for FramedWrite2<T>
     type SinkError = T::Error;
 
     fn start_send(&mut self, item: T::Item) -> StartSend<T::Item, T::Error> {
-        // If the buffer is already over 8KiB, then attempt to flush it. If after flushing it's
-        // *still* over 8KiB, then apply backpressure (reject the send).
-        if self.buffer.len() >= BACKPRESSURE_BOUNDARY {
+        // If the buffer is already over the high-water mark, then attempt to flush it. Until
+        // flushing gets it below the low-water mark, apply backpressure (reject the send). An
+        // empty buffer always takes a frame, so marks of 0 don't reject every send.
+        if self.paused || self.buffer.len() >= self.high_water_mark {
             try!(self.poll_complete());
 
-            if self.buffer.len() >= BACKPRESSURE_BOUNDARY {
+            self.paused = !self.buffer.is_empty() &&
+                self.buffer.len() >= self.low_water_mark();
+            if self.paused {
                 return Ok(AsyncSink::NotReady(item));
             }
         }
@@ -220,6 +316,10 @@ impl<T: Decoder> Decoder for FramedWrite2<T> {
     fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<T::Item>, T::Error> {
         self.inner.decode_eof(src)
     }
+
+    fn max_buf_size(&self) -> usize {
+        self.inner.max_buf_size()
+    }
 }
 
 impl<T: Read> Read for FramedWrite2<T> {
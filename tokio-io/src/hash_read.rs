@@ -0,0 +1,103 @@
+use std::{cmp, fmt};
+use std::io::{self, BufRead, Read};
+
+use digest::Digest;
+use digest::generic_array::GenericArray;
+
+use AsyncRead;
+
+/// A buffered reader which computes a hash of the data read through it.
+///
+/// The hash is updated with the bytes returned by `read` and, when reading
+/// through `BufRead`, with the bytes passed to `consume`, so only data which
+/// the application actually used is hashed, regardless of how often it was
+/// looked at through `fill_buf`. Once done, [`finalize`] returns the reader
+/// along with the hash, e.g. to compare it against an expected checksum.
+///
+/// This type is only available when the `digest` feature is enabled.
+///
+/// [`finalize`]: #method.finalize
+pub struct HashRead<R, H> {
+    inner: R,
+    hasher: H,
+}
+
+impl<R, H> HashRead<R, H>
+    where R: AsyncRead + BufRead,
+          H: Digest,
+{
+    /// Creates a new `HashRead` which hashes the data read from `inner`
+    /// using a new instance of `H`.
+    pub fn new(inner: R) -> HashRead<R, H> {
+        HashRead::with_hasher(inner, H::new())
+    }
+
+    /// Creates a new `HashRead` which feeds the data read from `inner` into
+    /// `hasher`.
+    pub fn with_hasher(inner: R, hasher: H) -> HashRead<R, H> {
+        HashRead {
+            inner: inner,
+            hasher: hasher,
+        }
+    }
+
+    /// Consumes the `HashRead`, returning the underlying reader and the hash
+    /// of all data read so far.
+    pub fn finalize(self) -> (R, GenericArray<u8, H::OutputSize>) {
+        (self.inner, self.hasher.result())
+    }
+}
+
+impl<R, H> HashRead<R, H> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Note that data read directly from the underlying reader isn't hashed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+}
+
+impl<R: AsyncRead + BufRead, H: Digest> Read for HashRead<R, H> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = try!(self.inner.read(buf));
+        self.hasher.input(&buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<R: AsyncRead + BufRead, H: Digest> BufRead for HashRead<R, H> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if amt > 0 {
+            // The consumed bytes were returned by the last `fill_buf` and are
+            // still buffered, so this can't block or fail.
+            if let Ok(buf) = self.inner.fill_buf() {
+                let amt = cmp::min(amt, buf.len());
+                self.hasher.input(&buf[..amt]);
+            }
+        }
+        self.inner.consume(amt)
+    }
+}
+
+impl<R: AsyncRead + BufRead, H: Digest> AsyncRead for HashRead<R, H> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<R: fmt::Debug, H> fmt::Debug for HashRead<R, H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HashRead")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
@@ -0,0 +1,98 @@
+use std::{cmp, fmt};
+use std::io::{self, BufRead, Read};
+
+use AsyncRead;
+
+/// A reader which passes all data read through it to a closure.
+///
+/// Created by the [`inspect`] function.
+///
+/// [`inspect`]: fn.inspect.html
+pub struct Inspect<R, F> {
+    inner: R,
+    f: F,
+    // Number of bytes at the start of the inner reader's buffer which were
+    // already passed to `f`.
+    inspected: usize,
+}
+
+/// Wraps a reader, calling `f` with the data read from it.
+///
+/// `f` is called with the slices returned by `fill_buf`, and with the data
+/// returned by `read`, before they are handed to the caller. Every byte is
+/// passed to `f` exactly once, even if `fill_buf` returns the same data
+/// several times before it is consumed, which makes this suitable for
+/// hashing or checksumming data flowing through a pipeline as well as for
+/// logging. All reads and `consume` calls are passed through to `inner`.
+pub fn inspect<R, F>(inner: R, f: F) -> Inspect<R, F>
+    where R: AsyncRead,
+          F: FnMut(&[u8]),
+{
+    Inspect {
+        inner: inner,
+        f: f,
+        inspected: 0,
+    }
+}
+
+impl<R, F> Inspect<R, F> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Note that data read directly from the underlying reader isn't passed
+    /// to the closure.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the `Inspect`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead, F: FnMut(&[u8])> Read for Inspect<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = try!(self.inner.read(buf));
+        // A buffered reader returns its buffered data first, part of which
+        // may have been inspected through `fill_buf` already.
+        let seen = cmp::min(n, self.inspected);
+        (self.f)(&buf[seen..n]);
+        self.inspected -= seen;
+        Ok(n)
+    }
+}
+
+impl<R: AsyncRead + BufRead, F: FnMut(&[u8])> BufRead for Inspect<R, F> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let buf = try!(self.inner.fill_buf());
+        if buf.len() > self.inspected {
+            (self.f)(&buf[self.inspected..]);
+            self.inspected = buf.len();
+        }
+        Ok(buf)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inspected = self.inspected.saturating_sub(amt);
+        self.inner.consume(amt)
+    }
+}
+
+impl<R: AsyncRead, F: FnMut(&[u8])> AsyncRead for Inspect<R, F> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<R: fmt::Debug, F> fmt::Debug for Inspect<R, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Inspect")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
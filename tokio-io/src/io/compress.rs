@@ -0,0 +1,182 @@
+//! Gzip compression for asynchronous I/O objects.
+//!
+//! This module is only available when the `flate2` feature is enabled.
+
+use std::cmp;
+use std::io::{self, BufRead, Read, Write};
+
+use bytes::BytesMut;
+use flate2::Compression;
+use flate2::bufread::GzDecoder;
+use flate2::write::GzEncoder;
+use futures::Poll;
+
+use {AsyncRead, AsyncWrite};
+
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Decompresses gzip data read from a buffered reader.
+///
+/// Reads return the decompressed data, which is decompressed a chunk at a
+/// time into an internal buffer, so `GzipDecoder` implements `BufRead` as
+/// well. A `WouldBlock` error from the underlying reader is passed on, and
+/// decompression continues where it left off on the next read. Reads return
+/// EOF once the end of the gzip member has been reached; any data following
+/// it is left in the underlying reader. Corrupt data results in an error of
+/// kind `InvalidInput` or `InvalidData`.
+#[derive(Debug)]
+pub struct GzipDecoder<R> {
+    // The reader before the first read, since creating the `GzDecoder`
+    // already starts reading the header.
+    reader: Option<R>,
+    decoder: Option<GzDecoder<R>>,
+    buf: BytesMut,
+}
+
+/// Compresses data with gzip before writing it to an underlying writer.
+///
+/// Data written is compressed into an internal buffer which is written out
+/// as it fills up, or when the `GzipEncoder` is flushed. Shutting down the
+/// `GzipEncoder` writes out the rest of the compressed data and the gzip
+/// trailer, and then shuts down the underlying writer.
+#[derive(Debug)]
+pub struct GzipEncoder<W: Write> {
+    inner: GzEncoder<W>,
+}
+
+impl<R: AsyncRead + BufRead> GzipDecoder<R> {
+    /// Creates a new `GzipDecoder` decompressing the data read from `reader`.
+    pub fn new(reader: R) -> GzipDecoder<R> {
+        GzipDecoder {
+            reader: Some(reader),
+            decoder: None,
+            buf: BytesMut::new(),
+        }
+    }
+
+    fn start(&mut self) {
+        if let Some(reader) = self.reader.take() {
+            self.decoder = Some(GzDecoder::new(reader));
+        }
+    }
+}
+
+impl<R> GzipDecoder<R> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        match self.reader {
+            Some(ref reader) => reader,
+            None => self.decoder.as_ref().unwrap().get_ref(),
+        }
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Note that reading directly from the underlying reader corrupts the
+    /// compressed stream.
+    pub fn get_mut(&mut self) -> &mut R {
+        match self.reader {
+            Some(ref mut reader) => reader,
+            None => self.decoder.as_mut().unwrap().get_mut(),
+        }
+    }
+
+    /// Consumes the `GzipDecoder`, returning the underlying reader.
+    ///
+    /// Any decompressed data which hasn't been read yet is lost.
+    pub fn into_inner(self) -> R {
+        match self.reader {
+            Some(reader) => reader,
+            None => self.decoder.unwrap().into_inner(),
+        }
+    }
+}
+
+impl<R: AsyncRead + BufRead> Read for GzipDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.buf.is_empty() && buf.len() >= CHUNK_SIZE {
+            self.start();
+            return self.decoder.as_mut().unwrap().read(buf);
+        }
+        let n = {
+            let mut rem = try!(self.fill_buf());
+            try!(rem.read(buf))
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: AsyncRead + BufRead> BufRead for GzipDecoder<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.buf.is_empty() {
+            self.start();
+            self.buf.resize(CHUNK_SIZE, 0);
+            let res = self.decoder.as_mut().unwrap().read(&mut self.buf);
+            let n = match res {
+                Ok(n) => n,
+                Err(e) => {
+                    self.buf.clear();
+                    return Err(e);
+                }
+            };
+            self.buf.truncate(n);
+        }
+        Ok(&self.buf)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = cmp::min(amt, self.buf.len());
+        let _ = self.buf.split_to(amt);
+    }
+}
+
+impl<R: AsyncRead + BufRead> AsyncRead for GzipDecoder<R> {
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+}
+
+impl<W: AsyncWrite> GzipEncoder<W> {
+    /// Creates a new `GzipEncoder` writing data compressed at the default
+    /// level to `writer`.
+    pub fn new(writer: W) -> GzipEncoder<W> {
+        GzipEncoder::with_level(writer, Compression::default())
+    }
+
+    /// Creates a new `GzipEncoder` writing data compressed at `level` to
+    /// `writer`.
+    pub fn with_level(writer: W, level: Compression) -> GzipEncoder<W> {
+        GzipEncoder { inner: GzEncoder::new(writer, level) }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Note that writing directly to the underlying writer corrupts the
+    /// compressed stream.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.get_mut()
+    }
+}
+
+impl<W: AsyncWrite> Write for GzipEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for GzipEncoder<W> {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        try_nb!(self.inner.try_finish());
+        self.inner.get_mut().shutdown()
+    }
+}
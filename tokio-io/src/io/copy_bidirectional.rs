@@ -0,0 +1,127 @@
+use std::io;
+
+use futures::{Async, Future, Poll};
+
+use {AsyncRead, AsyncWrite};
+
+/// A future which copies data in both directions between two I/O objects.
+///
+/// Created by the [`copy_bidirectional`] function.
+///
+/// [`copy_bidirectional`]: fn.copy_bidirectional.html
+#[derive(Debug)]
+pub struct CopyBidirectional<A, B> {
+    a: Option<A>,
+    b: Option<B>,
+    a_to_b: Transfer,
+    b_to_a: Transfer,
+}
+
+// The state of copying in one direction.
+#[derive(Debug)]
+struct Transfer {
+    read_done: bool,
+    // Set once the writer has been shut down, after which `amt` is final.
+    done: bool,
+    pos: usize,
+    cap: usize,
+    amt: u64,
+    buf: Box<[u8]>,
+}
+
+/// Creates a future which copies data from `a` to `b` and from `b` to `a`
+/// at the same time, e.g. to proxy a connection.
+///
+/// Both directions are driven concurrently, so data flowing one way never
+/// waits for the other. Once one of the objects reaches EOF, the other one
+/// is shut down for writing after all data read so far has been written to
+/// it, mirroring the half-close to the other side. The future completes
+/// once both directions are done.
+///
+/// On success the future resolves to the number of bytes copied from `a` to
+/// `b` and from `b` to `a`, along with both I/O objects. If either direction
+/// fails, the error is returned right away and the I/O objects are dropped.
+pub fn copy_bidirectional<A, B>(a: A, b: B) -> CopyBidirectional<A, B>
+    where A: AsyncRead + AsyncWrite,
+          B: AsyncRead + AsyncWrite,
+{
+    CopyBidirectional {
+        a: Some(a),
+        b: Some(b),
+        a_to_b: Transfer::new(),
+        b_to_a: Transfer::new(),
+    }
+}
+
+impl Transfer {
+    fn new() -> Transfer {
+        Transfer {
+            read_done: false,
+            done: false,
+            pos: 0,
+            cap: 0,
+            amt: 0,
+            buf: Box::new([0; 2048]),
+        }
+    }
+
+    fn poll<R, W>(&mut self, reader: &mut R, writer: &mut W) -> Poll<(), io::Error>
+        where R: AsyncRead,
+              W: AsyncWrite,
+    {
+        if self.done {
+            return Ok(Async::Ready(()));
+        }
+        loop {
+            if self.pos == self.cap && !self.read_done {
+                let n = try_ready!(reader.poll_read(&mut self.buf));
+                if n == 0 {
+                    self.read_done = true;
+                } else {
+                    self.pos = 0;
+                    self.cap = n;
+                }
+            }
+
+            while self.pos < self.cap {
+                let i = try_ready!(writer.poll_write(&self.buf[self.pos..self.cap]));
+                if i == 0 {
+                    return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                              "write zero byte into writer"));
+                }
+                self.pos += i;
+                self.amt += i as u64;
+            }
+
+            if self.read_done {
+                try_ready!(writer.shutdown());
+                self.done = true;
+                return Ok(Async::Ready(()));
+            }
+        }
+    }
+}
+
+impl<A, B> Future for CopyBidirectional<A, B>
+    where A: AsyncRead + AsyncWrite,
+          B: AsyncRead + AsyncWrite,
+{
+    type Item = (u64, u64, A, B);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(u64, u64, A, B), io::Error> {
+        let done = {
+            let a = self.a.as_mut().expect("poll CopyBidirectional after it's done");
+            let b = self.b.as_mut().unwrap();
+            let a_to_b = try!(self.a_to_b.poll(a, b)).is_ready();
+            let b_to_a = try!(self.b_to_a.poll(b, a)).is_ready();
+            a_to_b && b_to_a
+        };
+        if !done {
+            return Ok(Async::NotReady);
+        }
+        let a = self.a.take().unwrap();
+        let b = self.b.take().unwrap();
+        Ok((self.a_to_b.amt, self.b_to_a.amt, a, b).into())
+    }
+}
@@ -0,0 +1,83 @@
+use std::io::{self, BufRead};
+
+use futures::{Future, Poll};
+
+use {AsyncRead, AsyncWrite};
+
+/// A future which will copy all data from a buffered reader into a writer.
+///
+/// Created by the [`copy_buf`] function, this future will resolve to the
+/// number of bytes copied or an error if one happens.
+///
+/// [`copy_buf`]: fn.copy_buf.html
+#[derive(Debug)]
+pub struct CopyBuf<R, W> {
+    reader: Option<R>,
+    writer: Option<W>,
+    amt: u64,
+}
+
+/// Creates a future which represents copying all the bytes from a buffered
+/// reader to a writer.
+///
+/// This behaves like [`copy`], except that data is written straight out of
+/// the reader's own buffer (as returned by `BufRead::fill_buf`) rather than
+/// going through an intermediate buffer owned by the future. This future will
+/// only complete once the `reader` has hit EOF and all bytes have been written
+/// to and flushed from the `writer` provided.
+///
+/// On success the number of bytes is returned and the `reader` and `writer`
+/// are consumed. On error the error is returned and the I/O objects are
+/// consumed as well.
+///
+/// [`copy`]: fn.copy.html
+pub fn copy_buf<R, W>(reader: R, writer: W) -> CopyBuf<R, W>
+    where R: AsyncRead + BufRead,
+          W: AsyncWrite,
+{
+    CopyBuf {
+        reader: Some(reader),
+        writer: Some(writer),
+        amt: 0,
+    }
+}
+
+impl<R, W> Future for CopyBuf<R, W>
+    where R: AsyncRead + BufRead,
+          W: AsyncWrite,
+{
+    type Item = (u64, R, W);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(u64, R, W), io::Error> {
+        loop {
+            let n = {
+                let reader = self.reader.as_mut().unwrap();
+                let writer = self.writer.as_mut().unwrap();
+                let buf = try_nb!(reader.fill_buf());
+                if buf.is_empty() {
+                    0
+                } else {
+                    let i = try_ready!(writer.poll_write(buf));
+                    if i == 0 {
+                        return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                                  "write zero byte into writer"));
+                    }
+                    i
+                }
+            };
+
+            // An empty buffer from `fill_buf` means that the reader has hit
+            // EOF, so flush out the data and finish the transfer.
+            if n == 0 {
+                try_ready!(self.writer.as_mut().unwrap().poll_flush());
+                let reader = self.reader.take().unwrap();
+                let writer = self.writer.take().unwrap();
+                return Ok((self.amt, reader, writer).into())
+            }
+
+            self.reader.as_mut().unwrap().consume(n);
+            self.amt += n as u64;
+        }
+    }
+}
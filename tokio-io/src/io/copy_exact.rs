@@ -0,0 +1,79 @@
+use std::cmp;
+use std::io::{self, BufRead};
+
+use futures::{Future, Poll};
+
+use {AsyncRead, AsyncWrite};
+
+/// A future which will copy exactly `n` bytes from a buffered reader into a
+/// writer.
+///
+/// Created by the [`copy_exact`] function.
+///
+/// [`copy_exact`]: fn.copy_exact.html
+#[derive(Debug)]
+pub struct CopyExact<R, W> {
+    reader: Option<R>,
+    writer: Option<W>,
+    remaining: u64,
+}
+
+/// Creates a future which represents copying exactly `n` bytes from a
+/// buffered reader to a writer.
+///
+/// Like [`copy_buf`], data is written straight out of the reader's buffer,
+/// but no more than `n` bytes are consumed from `reader`, so it can be used
+/// to pass on a fixed-length payload and keep reading from `reader`
+/// afterwards. This future completes once `n` bytes have been written to and
+/// flushed from the `writer`, resolving to both I/O objects.
+///
+/// If `reader` hits EOF before `n` bytes were copied, the future resolves
+/// to an error of kind `UnexpectedEof`. In the case of an error the I/O
+/// objects are consumed.
+///
+/// [`copy_buf`]: fn.copy_buf.html
+pub fn copy_exact<R, W>(reader: R, writer: W, n: u64) -> CopyExact<R, W>
+    where R: AsyncRead + BufRead,
+          W: AsyncWrite,
+{
+    CopyExact {
+        reader: Some(reader),
+        writer: Some(writer),
+        remaining: n,
+    }
+}
+
+impl<R, W> Future for CopyExact<R, W>
+    where R: AsyncRead + BufRead,
+          W: AsyncWrite,
+{
+    type Item = (R, W);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(R, W), io::Error> {
+        while self.remaining > 0 {
+            let n = {
+                let reader = self.reader.as_mut().expect("poll CopyExact after it's done");
+                let writer = self.writer.as_mut().unwrap();
+                let buf = try_nb!(reader.fill_buf());
+                if buf.is_empty() {
+                    return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "early eof"));
+                }
+                let len = cmp::min(buf.len() as u64, self.remaining) as usize;
+                let i = try_ready!(writer.poll_write(&buf[..len]));
+                if i == 0 {
+                    return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                              "write zero byte into writer"));
+                }
+                i
+            };
+            self.reader.as_mut().unwrap().consume(n);
+            self.remaining -= n as u64;
+        }
+
+        try_ready!(self.writer.as_mut().expect("poll CopyExact after it's done").poll_flush());
+        let reader = self.reader.take().unwrap();
+        let writer = self.writer.take().unwrap();
+        Ok((reader, writer).into())
+    }
+}
@@ -3,30 +3,113 @@
 //! Contains various combinators to work with I/O objects and type definitions
 //! as well.
 //!
+//! The combinators take their I/O object by value and hand it back once they
+//! complete. Since both `AsyncRead` and `BufRead` are implemented for `&mut R`,
+//! a reader can also be lent to a combinator through `Read::by_ref` and used
+//! again afterwards, without threading it through the returned value.
+//!
+//! Likewise `Box<R>` implements both traits whenever `R` does, including
+//! unsized trait objects. Readers of different types can therefore be used
+//! with the buffered combinators through a common trait object, given a trait
+//! which has `AsyncRead` and `BufRead` as supertraits.
+//!
 //! A description of the high-level I/O combinators can be [found online] in
 //! addition to a description of the [low level details].
 //!
 //! [found online]: https://tokio.rs/docs/getting-started/core/
 //! [low level details]: https://tokio.rs/docs/going-deeper-tokio/core-low-level/
 
+#[cfg(feature = "flate2")]
+pub mod compress;
+
 mod copy;
+mod copy_bidirectional;
+mod copy_buf;
+mod copy_exact;
 mod flush;
 mod read;
 mod read_exact;
+mod read_exact_buf;
+mod read_frame;
+mod read_int;
+mod read_into_buf;
+mod read_n_bytes;
+mod read_pascal_string;
+mod read_to_buf;
 mod read_to_end;
+mod read_to_end_buf;
+mod read_to_string_buf;
 mod read_until;
+mod read_until_any;
+mod read_while;
 mod shutdown;
+mod skip_bytes;
+mod skip_until;
+mod split_once;
 mod write_all;
 
 pub use allow_std::AllowStdIo;
+pub use blocking::{blocking_read, blocking_write, BlockingRead, BlockingWrite};
+pub use buf_reader::{BufReader, Peek};
+pub use buf_stream::BufStream;
+pub use buf_writer::{BufWriter, FlushBuf};
 pub use self::copy::{copy, Copy};
+pub use self::copy_bidirectional::{copy_bidirectional, CopyBidirectional};
+pub use self::copy_buf::{copy_buf, CopyBuf};
+pub use self::copy_exact::{copy_exact, CopyExact};
+pub use count_bytes::{count_bytes, CountBytes};
+pub use count_write::{count_write, CountWrite};
+#[cfg(feature = "crc32")]
+pub use crc_read::{crc_read, CrcRead, VerifyCrc};
+pub use duplex::{duplex, DuplexStream};
+pub use empty::{empty, Empty};
 pub use self::flush::{flush, Flush};
-pub use lines::{lines, Lines};
+#[cfg(feature = "digest")]
+pub use hash_read::HashRead;
+pub use inspect::{inspect, Inspect};
+pub use limited_read::LimitedRead;
+pub use limited_write::LimitedWrite;
+pub use lines::{lines, lines_bytes, parse_lines, Lines, LinesBytes, ParseLines};
+pub use merge::{merge, Merge};
+pub use pipe::{pipe, PipeReader, PipeWriter};
 pub use self::read::{read, Read};
+pub use read_buf::ReadBuf;
 pub use self::read_exact::{read_exact, ReadExact};
+pub use self::read_exact_buf::{read_exact_buf, ReadExactBuf};
+pub use self::read_frame::{read_frame, ReadFrame};
+pub use self::read_int::{read_i8, read_i16_be, read_i16_le, read_i32_be, read_i32_le,
+                         read_i64_be, read_i64_le, read_u8, read_u16_be, read_u16_le,
+                         read_u32_be, read_u32_le, read_u64_be, read_u64_le};
+pub use self::read_int::{ReadI8, ReadI16Be, ReadI16Le, ReadI32Be, ReadI32Le, ReadI64Be,
+                         ReadI64Le, ReadU8, ReadU16Be, ReadU16Le, ReadU32Be, ReadU32Le,
+                         ReadU64Be, ReadU64Le};
+pub use self::read_into_buf::{read_into_buf, ReadIntoBuf};
+pub use self::read_n_bytes::{read_n_bytes, ReadNBytes, DEFAULT_MAX_READ_N_BYTES};
+pub use self::read_pascal_string::{read_pascal_string, read_pascal_string_u16_be,
+                                   ReadPascalString};
+pub use self::read_to_buf::{read_to_buf, ReadToBuf};
 pub use self::read_to_end::{read_to_end, ReadToEnd};
+pub use self::read_to_end_buf::{read_to_end_buf, ReadToEndBuf};
+pub use self::read_to_string_buf::{read_to_string_buf, ReadToStringBuf};
 pub use self::read_until::{read_until, ReadUntil};
+pub use self::read_until_any::{read_until_any, ReadUntilAny};
+pub use self::read_while::{read_while, ReadWhile};
+pub use repeat::{repeat, Repeat};
+pub use scan::{scan, Scan};
 pub use self::shutdown::{shutdown, Shutdown};
-pub use split::{ReadHalf, WriteHalf};
+pub use std::io::{sink, Sink};
+pub use self::skip_bytes::{skip_bytes, SkipBytes};
+pub use self::skip_until::{skip_until, SkipUntil};
+pub use split::{split, ReadHalf, WriteHalf};
+pub use split_at_limit::{split_at_limit, Remainder, Take};
+pub use self::split_once::{split_once, SplitOnce};
+pub use split_str::{split_str, SplitStr};
+pub use stdio::{stderr, stdin, stdout, Stderr, Stdin, Stdout};
+pub use stream_reader::StreamReader;
+pub use tee::TeeRead;
+pub use timeout::{Clock, Timeout};
 pub use window::Window;
+pub use windows::{windows, Windows};
 pub use self::write_all::{write_all, WriteAll};
+pub use writer::{writer, Writer};
+pub use zero::{zero, Zero};
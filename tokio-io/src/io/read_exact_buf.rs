@@ -0,0 +1,94 @@
+use std::cmp;
+use std::io::{self, BufRead};
+use std::mem;
+
+use futures::{Poll, Future};
+
+use AsyncRead;
+
+/// A future which can be used to easily read exactly enough bytes from a
+/// buffered reader to fill a buffer.
+///
+/// Created by the [`read_exact_buf`] function.
+///
+/// [`read_exact_buf`]: fn.read_exact_buf.html
+#[derive(Debug)]
+pub struct ReadExactBuf<A, T> {
+    state: State<A, T>,
+}
+
+#[derive(Debug)]
+enum State<A, T> {
+    Reading {
+        a: A,
+        buf: T,
+        pos: usize,
+    },
+    Empty,
+}
+
+/// Creates a future which will read exactly enough bytes to fill `buf` from a
+/// buffered reader, returning an error if EOF is hit sooner.
+///
+/// This behaves like [`read_exact`], except that bytes are copied out of the
+/// reader's internal buffer through `BufRead::fill_buf` and `consume`. Data
+/// which is already buffered is used first and the underlying reader is only
+/// asked for more once the buffer is drained, so no more bytes than needed
+/// are consumed from `a`.
+///
+/// If EOF is hit before `buf` is full, the future resolves to an error of kind
+/// `UnexpectedEof`. In the case of an error the buffer and the object will be
+/// discarded, with the error yielded.
+///
+/// [`read_exact`]: fn.read_exact.html
+pub fn read_exact_buf<A, T>(a: A, buf: T) -> ReadExactBuf<A, T>
+    where A: AsyncRead + BufRead,
+          T: AsMut<[u8]>,
+{
+    ReadExactBuf {
+        state: State::Reading {
+            a: a,
+            buf: buf,
+            pos: 0,
+        },
+    }
+}
+
+fn eof() -> io::Error {
+    io::Error::new(io::ErrorKind::UnexpectedEof, "early eof")
+}
+
+impl<A, T> Future for ReadExactBuf<A, T>
+    where A: AsyncRead + BufRead,
+          T: AsMut<[u8]>,
+{
+    type Item = (A, T);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, T), io::Error> {
+        match self.state {
+            State::Reading { ref mut a, ref mut buf, ref mut pos } => {
+                let buf = buf.as_mut();
+                while *pos < buf.len() {
+                    let n = {
+                        let available = try_nb!(a.fill_buf());
+                        if available.is_empty() {
+                            return Err(eof())
+                        }
+                        let n = cmp::min(available.len(), buf.len() - *pos);
+                        buf[*pos..*pos + n].copy_from_slice(&available[..n]);
+                        n
+                    };
+                    a.consume(n);
+                    *pos += n;
+                }
+            }
+            State::Empty => panic!("poll a ReadExactBuf after it's done"),
+        }
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Reading { a, buf, .. } => Ok((a, buf).into()),
+            State::Empty => panic!(),
+        }
+    }
+}
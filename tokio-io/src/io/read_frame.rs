@@ -0,0 +1,109 @@
+use std::io::{self, BufRead};
+
+use bytes::{BigEndian, ByteOrder, LittleEndian};
+use futures::{Future, Poll};
+
+use AsyncRead;
+use super::read_exact::{read_exact, ReadExact};
+use super::read_n_bytes::{read_n_bytes, ReadNBytes};
+
+/// A future which reads a fixed-size header followed by a body whose length
+/// is given in the header.
+///
+/// Created by the [`read_frame`] function.
+///
+/// [`read_frame`]: fn.read_frame.html
+#[derive(Debug)]
+pub struct ReadFrame<A, H> {
+    state: State<A, H>,
+    length_offset: usize,
+    length_size: usize,
+    little_endian: bool,
+}
+
+#[derive(Debug)]
+enum State<A, H> {
+    Header(ReadExact<A, H>),
+    Body(Option<H>, ReadNBytes<A>),
+}
+
+/// Creates a future which reads a header into `header`, and then a body of
+/// the length stored in the header.
+///
+/// The header is read to fill `header` completely, e.g. `[0; 8]` for an 8
+/// byte header. The length of the body is stored in `length_size` bytes at
+/// `length_offset` into the header, as a big-endian unsigned integer by
+/// default; call [`little_endian`] on the returned future if it's
+/// little-endian. The length only counts the body, not the header. Bodies
+/// are limited to [`DEFAULT_MAX_READ_N_BYTES`] like in [`read_n_bytes`].
+///
+/// The future resolves to the reader, the header and the body. If EOF is hit
+/// before the whole frame was read, it resolves to an error of kind
+/// `UnexpectedEof`.
+///
+/// # Panics
+///
+/// Panics if `length_size` isn't between 1 and 8, or if the length field
+/// doesn't fit into the header.
+///
+/// [`little_endian`]: struct.ReadFrame.html#method.little_endian
+/// [`DEFAULT_MAX_READ_N_BYTES`]: constant.DEFAULT_MAX_READ_N_BYTES.html
+/// [`read_n_bytes`]: fn.read_n_bytes.html
+pub fn read_frame<A, H>(a: A, mut header: H, length_offset: usize, length_size: usize)
+                        -> ReadFrame<A, H>
+    where A: AsyncRead + BufRead,
+          H: AsMut<[u8]>,
+{
+    assert!(length_size >= 1 && length_size <= 8,
+            "length field must be between 1 and 8 bytes");
+    assert!(length_offset + length_size <= header.as_mut().len(),
+            "length field out of the header's bounds");
+    ReadFrame {
+        state: State::Header(read_exact(a, header)),
+        length_offset: length_offset,
+        length_size: length_size,
+        little_endian: false,
+    }
+}
+
+impl<A, H> ReadFrame<A, H> {
+    /// Reads the length field as a little-endian integer.
+    pub fn little_endian(mut self) -> ReadFrame<A, H> {
+        self.little_endian = true;
+        self
+    }
+}
+
+impl<A, H> Future for ReadFrame<A, H>
+    where A: AsyncRead + BufRead,
+          H: AsMut<[u8]>,
+{
+    type Item = (A, H, Vec<u8>);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, H, Vec<u8>), io::Error> {
+        loop {
+            let (a, mut header) = match self.state {
+                State::Header(ref mut f) => try_ready!(f.poll()),
+                State::Body(ref mut header, ref mut f) => {
+                    let (a, body) = try_ready!(f.poll());
+                    let header = header.take().expect("poll ReadFrame after it's done");
+                    return Ok((a, header, body).into());
+                }
+            };
+            let len = {
+                let field = &header.as_mut()[self.length_offset..][..self.length_size];
+                if self.little_endian {
+                    LittleEndian::read_uint(field, self.length_size)
+                } else {
+                    BigEndian::read_uint(field, self.length_size)
+                }
+            };
+            if len > usize::max_value() as u64 {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          "frame length out of range"));
+            }
+            self.state = State::Body(Some(header), read_n_bytes(a, len as usize));
+        }
+    }
+}
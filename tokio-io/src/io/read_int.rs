@@ -0,0 +1,81 @@
+use std::io::{self, BufRead};
+
+use bytes::{BigEndian, ByteOrder, LittleEndian};
+use futures::{Future, Poll};
+
+use AsyncRead;
+use super::read_n_bytes::{read_n_bytes, ReadNBytes};
+
+macro_rules! read_int {
+    ($(#[$doc:meta] fn $name:ident -> $future:ident($ty:ident, $len:expr, $read:expr);)*) => {
+        $(
+            #[$doc]
+            ///
+            /// The bytes are read from the reader's buffer with
+            /// [`read_n_bytes`]. If EOF is hit before the whole value was
+            /// read, the future resolves to an error of kind
+            /// `UnexpectedEof`.
+            ///
+            /// [`read_n_bytes`]: fn.read_n_bytes.html
+            pub fn $name<A>(a: A) -> $future<A>
+                where A: AsyncRead + BufRead,
+            {
+                $future {
+                    inner: read_n_bytes(a, $len),
+                }
+            }
+
+            /// A future which reads an integer from a buffered reader.
+            #[derive(Debug)]
+            pub struct $future<A> {
+                inner: ReadNBytes<A>,
+            }
+
+            impl<A> Future for $future<A>
+                where A: AsyncRead + BufRead,
+            {
+                type Item = (A, $ty);
+                type Error = io::Error;
+
+                fn poll(&mut self) -> Poll<(A, $ty), io::Error> {
+                    let (a, buf) = try_ready!(self.inner.poll());
+                    Ok((a, ($read)(&buf[..])).into())
+                }
+            }
+        )*
+    }
+}
+
+read_int! {
+    /// Creates a future which reads a `u8` from `a`.
+    fn read_u8 -> ReadU8(u8, 1, |buf: &[u8]| buf[0]);
+    /// Creates a future which reads an `i8` from `a`.
+    fn read_i8 -> ReadI8(i8, 1, |buf: &[u8]| buf[0] as i8);
+
+    /// Creates a future which reads a big-endian `u16` from `a`.
+    fn read_u16_be -> ReadU16Be(u16, 2, BigEndian::read_u16);
+    /// Creates a future which reads a little-endian `u16` from `a`.
+    fn read_u16_le -> ReadU16Le(u16, 2, LittleEndian::read_u16);
+    /// Creates a future which reads a big-endian `i16` from `a`.
+    fn read_i16_be -> ReadI16Be(i16, 2, BigEndian::read_i16);
+    /// Creates a future which reads a little-endian `i16` from `a`.
+    fn read_i16_le -> ReadI16Le(i16, 2, LittleEndian::read_i16);
+
+    /// Creates a future which reads a big-endian `u32` from `a`.
+    fn read_u32_be -> ReadU32Be(u32, 4, BigEndian::read_u32);
+    /// Creates a future which reads a little-endian `u32` from `a`.
+    fn read_u32_le -> ReadU32Le(u32, 4, LittleEndian::read_u32);
+    /// Creates a future which reads a big-endian `i32` from `a`.
+    fn read_i32_be -> ReadI32Be(i32, 4, BigEndian::read_i32);
+    /// Creates a future which reads a little-endian `i32` from `a`.
+    fn read_i32_le -> ReadI32Le(i32, 4, LittleEndian::read_i32);
+
+    /// Creates a future which reads a big-endian `u64` from `a`.
+    fn read_u64_be -> ReadU64Be(u64, 8, BigEndian::read_u64);
+    /// Creates a future which reads a little-endian `u64` from `a`.
+    fn read_u64_le -> ReadU64Le(u64, 8, LittleEndian::read_u64);
+    /// Creates a future which reads a big-endian `i64` from `a`.
+    fn read_i64_be -> ReadI64Be(i64, 8, BigEndian::read_i64);
+    /// Creates a future which reads a little-endian `i64` from `a`.
+    fn read_i64_le -> ReadI64Le(i64, 8, LittleEndian::read_i64);
+}
@@ -0,0 +1,68 @@
+use std::io;
+use std::mem;
+
+use bytes::BufMut;
+use futures::{Future, Poll};
+
+use AsyncRead;
+
+#[derive(Debug)]
+enum State<R, B> {
+    Pending {
+        rd: R,
+        buf: B,
+    },
+    Empty,
+}
+
+/// Tries to read some bytes into the remaining capacity of a `BufMut`, such
+/// as a `BytesMut`, returning a future type.
+///
+/// Unlike [`read`] the buffer doesn't need to be sized up front: the bytes
+/// are written into the buffer's unused capacity with
+/// [`AsyncRead::read_buf`], and the buffer is advanced past them, so for a
+/// `BytesMut` reading and making room for more data comes down to calling
+/// `reserve` in between reads. Note that a buffer without any remaining
+/// capacity reads 0 bytes, which looks like EOF.
+///
+/// The returned future will resolve to both the I/O stream and the buffer
+/// as well as the number of bytes read once the read operation is completed.
+///
+/// [`read`]: fn.read.html
+/// [`AsyncRead::read_buf`]: ../trait.AsyncRead.html#method.read_buf
+pub fn read_into_buf<R, B>(rd: R, buf: B) -> ReadIntoBuf<R, B>
+    where R: AsyncRead,
+          B: BufMut,
+{
+    ReadIntoBuf { state: State::Pending { rd: rd, buf: buf } }
+}
+
+/// A future which reads some bytes into a `BufMut`.
+///
+/// Created by the [`read_into_buf`] function.
+///
+/// [`read_into_buf`]: fn.read_into_buf.html
+#[derive(Debug)]
+pub struct ReadIntoBuf<R, B> {
+    state: State<R, B>,
+}
+
+impl<R, B> Future for ReadIntoBuf<R, B>
+    where R: AsyncRead,
+          B: BufMut,
+{
+    type Item = (R, B, usize);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(R, B, usize), io::Error> {
+        let nread = match self.state {
+            State::Pending { ref mut rd, ref mut buf } => try_ready!(rd.read_buf(buf)),
+            State::Empty => panic!("poll a ReadIntoBuf after it's done"),
+        };
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Pending { rd, buf } => Ok((rd, buf, nread).into()),
+            State::Empty => panic!("invalid internal state"),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::cmp;
+use std::io::{self, BufRead};
+use std::mem;
+
+use futures::{Poll, Future};
+
+use AsyncRead;
+
+/// The default limit on the number of bytes a [`ReadNBytes`] future reads.
+///
+/// [`ReadNBytes`]: struct.ReadNBytes.html
+pub const DEFAULT_MAX_READ_N_BYTES: usize = 8 * 1024 * 1024;
+
+/// A future which reads exactly `n` bytes from a buffered reader into a new
+/// vector.
+///
+/// Created by the [`read_n_bytes`] function.
+///
+/// [`read_n_bytes`]: fn.read_n_bytes.html
+#[derive(Debug)]
+pub struct ReadNBytes<A> {
+    state: State<A>,
+}
+
+#[derive(Debug)]
+enum State<A> {
+    Reading {
+        a: A,
+        buf: Vec<u8>,
+        n: usize,
+        max_len: usize,
+    },
+    Empty,
+}
+
+/// Creates a future which will read exactly `n` bytes from a buffered reader
+/// into a newly allocated `Vec<u8>`.
+///
+/// This saves allocating and sizing a buffer before calling
+/// [`read_exact_buf`]. The bytes are copied out of the reader's internal
+/// buffer, so no more bytes than needed are consumed from `a`.
+///
+/// To guard against huge allocations when `n` comes from untrusted input,
+/// the future fails with an error of kind `InvalidInput` without reading
+/// anything if `n` is larger than [`DEFAULT_MAX_READ_N_BYTES`]. The limit can
+/// be changed with [`ReadNBytes::max_len`].
+///
+/// If EOF is hit before `n` bytes were read, the future resolves to an error
+/// of kind `UnexpectedEof`. In the case of an error the object will be
+/// discarded, with the error yielded.
+///
+/// [`read_exact_buf`]: fn.read_exact_buf.html
+/// [`DEFAULT_MAX_READ_N_BYTES`]: constant.DEFAULT_MAX_READ_N_BYTES.html
+/// [`ReadNBytes::max_len`]: struct.ReadNBytes.html#method.max_len
+pub fn read_n_bytes<A>(a: A, n: usize) -> ReadNBytes<A>
+    where A: AsyncRead + BufRead,
+{
+    ReadNBytes {
+        state: State::Reading {
+            a: a,
+            buf: Vec::new(),
+            n: n,
+            max_len: DEFAULT_MAX_READ_N_BYTES,
+        },
+    }
+}
+
+impl<A> ReadNBytes<A> {
+    /// Sets the maximum number of bytes this future may be asked to read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the future has completed.
+    pub fn max_len(mut self, max_len: usize) -> ReadNBytes<A> {
+        match self.state {
+            State::Reading { max_len: ref mut max, .. } => *max = max_len,
+            State::Empty => panic!("ReadNBytes already completed"),
+        }
+        self
+    }
+}
+
+impl<A> Future for ReadNBytes<A>
+    where A: AsyncRead + BufRead,
+{
+    type Item = (A, Vec<u8>);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, Vec<u8>), io::Error> {
+        match self.state {
+            State::Reading { ref mut a, ref mut buf, n, max_len } => {
+                if n > max_len {
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                              "requested length exceeds limit"));
+                }
+                if buf.capacity() < n {
+                    buf.reserve_exact(n);
+                }
+                while buf.len() < n {
+                    let used = {
+                        let available = try_nb!(a.fill_buf());
+                        if available.is_empty() {
+                            return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                                      "early eof"));
+                        }
+                        let used = cmp::min(available.len(), n - buf.len());
+                        buf.extend_from_slice(&available[..used]);
+                        used
+                    };
+                    a.consume(used);
+                }
+            }
+            State::Empty => panic!("poll a ReadNBytes after it's done"),
+        }
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Reading { a, buf, .. } => Ok((a, buf).into()),
+            State::Empty => panic!(),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::io::{self, BufRead};
+
+use futures::{Future, Poll};
+
+use AsyncRead;
+use super::read_int::{read_u8, read_u16_be, ReadU8, ReadU16Be};
+use super::read_n_bytes::{read_n_bytes, ReadNBytes};
+
+/// A future which reads a length-prefixed byte string from a buffered
+/// reader.
+///
+/// Created by the [`read_pascal_string`] and [`read_pascal_string_u16_be`]
+/// functions.
+///
+/// [`read_pascal_string`]: fn.read_pascal_string.html
+/// [`read_pascal_string_u16_be`]: fn.read_pascal_string_u16_be.html
+#[derive(Debug)]
+pub struct ReadPascalString<A> {
+    state: State<A>,
+}
+
+#[derive(Debug)]
+enum State<A> {
+    LengthU8(ReadU8<A>),
+    LengthU16Be(ReadU16Be<A>),
+    Data(ReadNBytes<A>),
+}
+
+/// Creates a future which reads a byte string prefixed by its length as a
+/// single byte from `a`.
+///
+/// The future resolves to the reader and the bytes following the length
+/// prefix, which are not included. If EOF is hit before the whole string was
+/// read, the future resolves to an error of kind `UnexpectedEof`.
+pub fn read_pascal_string<A>(a: A) -> ReadPascalString<A>
+    where A: AsyncRead + BufRead,
+{
+    ReadPascalString { state: State::LengthU8(read_u8(a)) }
+}
+
+/// Creates a future which reads a byte string prefixed by its length as a
+/// big-endian `u16` from `a`.
+///
+/// This works like [`read_pascal_string`] apart from the size of the length
+/// prefix.
+///
+/// [`read_pascal_string`]: fn.read_pascal_string.html
+pub fn read_pascal_string_u16_be<A>(a: A) -> ReadPascalString<A>
+    where A: AsyncRead + BufRead,
+{
+    ReadPascalString { state: State::LengthU16Be(read_u16_be(a)) }
+}
+
+impl<A> Future for ReadPascalString<A>
+    where A: AsyncRead + BufRead,
+{
+    type Item = (A, Vec<u8>);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, Vec<u8>), io::Error> {
+        loop {
+            let (a, len) = match self.state {
+                State::LengthU8(ref mut f) => {
+                    let (a, len) = try_ready!(f.poll());
+                    (a, len as usize)
+                }
+                State::LengthU16Be(ref mut f) => {
+                    let (a, len) = try_ready!(f.poll());
+                    (a, len as usize)
+                }
+                State::Data(ref mut f) => return f.poll(),
+            };
+            self.state = State::Data(read_n_bytes(a, len));
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::cmp;
+use std::io;
+use std::mem;
+
+use bytes::{BufMut, BytesMut};
+use futures::{Poll, Future};
+
+use AsyncRead;
+
+// The smallest amount of space reserved when the buffer is full.
+const MIN_RESERVE: usize = 64;
+
+/// A future which can be used to easily read the entire contents of a stream
+/// into a `BytesMut`.
+///
+/// Created by the [`read_to_buf`] function.
+///
+/// [`read_to_buf`]: fn.read_to_buf.html
+#[derive(Debug)]
+pub struct ReadToBuf<A> {
+    state: State<A>,
+}
+
+#[derive(Debug)]
+enum State<A> {
+    Reading {
+        a: A,
+        buf: BytesMut,
+    },
+    Empty,
+}
+
+/// Creates a future which will read all the bytes associated with the I/O
+/// object `A` into the `BytesMut` provided.
+///
+/// This is like [`read_to_end`], but appends to a `BytesMut`, so the data can
+/// be handed to codecs or frozen into `Bytes` without a copy. Whenever the
+/// buffer is full, it's grown by reserving at least as much space as it
+/// already holds, and the data is read straight into its spare capacity.
+///
+/// In the case of an error the buffer and the object will be discarded, with
+/// the error yielded. In the case of success the object and the buffer will
+/// be returned, with all data read from the stream appended to the buffer.
+///
+/// [`read_to_end`]: fn.read_to_end.html
+pub fn read_to_buf<A>(a: A, buf: BytesMut) -> ReadToBuf<A>
+    where A: AsyncRead,
+{
+    ReadToBuf {
+        state: State::Reading {
+            a: a,
+            buf: buf,
+        }
+    }
+}
+
+impl<A> Future for ReadToBuf<A>
+    where A: AsyncRead,
+{
+    type Item = (A, BytesMut);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, BytesMut), io::Error> {
+        match self.state {
+            State::Reading { ref mut a, ref mut buf } => {
+                loop {
+                    if !buf.has_remaining_mut() {
+                        let additional = cmp::max(buf.len(), MIN_RESERVE);
+                        buf.reserve(additional);
+                    }
+                    if try_ready!(a.read_buf(buf)) == 0 {
+                        break;
+                    }
+                }
+            }
+            State::Empty => panic!("poll ReadToBuf after it's done"),
+        }
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Reading { a, buf } => Ok((a, buf).into()),
+            State::Empty => unreachable!(),
+        }
+    }
+}
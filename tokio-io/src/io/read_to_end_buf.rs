@@ -0,0 +1,81 @@
+use std::io::{self, BufRead};
+use std::mem;
+
+use futures::{Poll, Future};
+
+use AsyncRead;
+
+/// A future which can be used to easily read the entire contents of a
+/// buffered stream into a vector.
+///
+/// Created by the [`read_to_end_buf`] function.
+///
+/// [`read_to_end_buf`]: fn.read_to_end_buf.html
+#[derive(Debug)]
+pub struct ReadToEndBuf<A> {
+    state: State<A>,
+}
+
+#[derive(Debug)]
+enum State<A> {
+    Reading {
+        a: A,
+        buf: Vec<u8>,
+    },
+    Empty,
+}
+
+/// Creates a future which will read all the bytes associated with the
+/// buffered I/O object `A` into the buffer provided.
+///
+/// This behaves like [`read_to_end`], except that data is appended straight
+/// from the reader's internal buffer as returned by `BufRead::fill_buf`,
+/// instead of first being read into a zeroed region of `buf`.
+///
+/// In the case of an error the buffer and the object will be discarded, with
+/// the error yielded. In the case of success the object will be destroyed and
+/// the buffer will be returned, with all data read from the stream appended to
+/// the buffer.
+///
+/// [`read_to_end`]: fn.read_to_end.html
+pub fn read_to_end_buf<A>(a: A, buf: Vec<u8>) -> ReadToEndBuf<A>
+    where A: AsyncRead + BufRead,
+{
+    ReadToEndBuf {
+        state: State::Reading {
+            a: a,
+            buf: buf,
+        }
+    }
+}
+
+impl<A> Future for ReadToEndBuf<A>
+    where A: AsyncRead + BufRead,
+{
+    type Item = (A, Vec<u8>);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, Vec<u8>), io::Error> {
+        match self.state {
+            State::Reading { ref mut a, ref mut buf } => {
+                loop {
+                    let n = {
+                        let available = try_nb!(a.fill_buf());
+                        buf.extend_from_slice(available);
+                        available.len()
+                    };
+                    if n == 0 {
+                        break;
+                    }
+                    a.consume(n);
+                }
+            },
+            State::Empty => panic!("poll ReadToEndBuf after it's done"),
+        }
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Reading { a, buf } => Ok((a, buf).into()),
+            State::Empty => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::io::{self, BufRead};
+use std::{mem, str};
+
+use futures::{Poll, Future};
+
+use AsyncRead;
+
+/// A future which can be used to easily read the entire contents of a
+/// buffered stream into a string.
+///
+/// Created by the [`read_to_string_buf`] function.
+///
+/// [`read_to_string_buf`]: fn.read_to_string_buf.html
+#[derive(Debug)]
+pub struct ReadToStringBuf<A> {
+    state: State<A>,
+}
+
+#[derive(Debug)]
+enum State<A> {
+    Reading {
+        a: A,
+        // The bytes of the string, followed by those of a UTF-8 sequence
+        // which was cut off at the end of the last chunk returned by
+        // `fill_buf`
+        buf: Vec<u8>,
+        // The length of the validated part of `buf`
+        valid: usize,
+    },
+    Empty,
+}
+
+/// Creates a future which will read all the bytes associated with the
+/// buffered I/O object `A` into the string provided.
+///
+/// Each chunk returned by `BufRead::fill_buf` is validated as UTF-8 as soon
+/// as it is read, so invalid data is detected without reading the rest of the
+/// stream. A multi-byte character which is split across two chunks is handled
+/// correctly.
+///
+/// If the data is not valid UTF-8 the future resolves to an error of kind
+/// `InvalidData`. The text read before an error, whether it's invalid data or
+/// an I/O error, is kept and can be taken out of the future with
+/// [`into_inner`]. In the case of success the object will be destroyed and the
+/// buffer will be returned, with all data read from the stream appended to the
+/// buffer.
+///
+/// [`into_inner`]: struct.ReadToStringBuf.html#method.into_inner
+pub fn read_to_string_buf<A>(a: A, buf: String) -> ReadToStringBuf<A>
+    where A: AsyncRead + BufRead,
+{
+    let valid = buf.len();
+    ReadToStringBuf {
+        state: State::Reading {
+            a: a,
+            buf: buf.into_bytes(),
+            valid: valid,
+        }
+    }
+}
+
+fn invalid_utf8() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData,
+                   "stream did not contain valid UTF-8")
+}
+
+impl<A> ReadToStringBuf<A> {
+    /// Consumes the future, returning the I/O object and the string read so
+    /// far.
+    ///
+    /// After the future resolved to an error, the string holds all the text
+    /// read up to the invalid data or the I/O error. A character which was
+    /// only read in part is left out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the future already resolved successfully.
+    pub fn into_inner(self) -> (A, String) {
+        match self.state {
+            State::Reading { a, mut buf, valid } => {
+                buf.truncate(valid);
+                (a, unsafe { String::from_utf8_unchecked(buf) })
+            }
+            State::Empty => panic!("ReadToStringBuf already resolved"),
+        }
+    }
+}
+
+impl<A> Future for ReadToStringBuf<A>
+    where A: AsyncRead + BufRead,
+{
+    type Item = (A, String);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, String), io::Error> {
+        match self.state {
+            State::Reading { ref mut a, ref mut buf, ref mut valid } => {
+                loop {
+                    let n = {
+                        let available = try_nb!(a.fill_buf());
+                        buf.extend_from_slice(available);
+                        available.len()
+                    };
+                    if n == 0 {
+                        if *valid < buf.len() {
+                            return Err(invalid_utf8());
+                        }
+                        break;
+                    }
+                    a.consume(n);
+                    // Only the new bytes, and a sequence cut off before them,
+                    // need to be validated.
+                    match str::from_utf8(&buf[*valid..]) {
+                        Ok(_) => *valid = buf.len(),
+                        Err(e) => {
+                            *valid += e.valid_up_to();
+                            if e.error_len().is_some() {
+                                return Err(invalid_utf8());
+                            }
+                        }
+                    }
+                }
+            },
+            State::Empty => panic!("poll ReadToStringBuf after it's done"),
+        }
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Reading { a, buf, .. } => {
+                Ok((a, unsafe { String::from_utf8_unchecked(buf) }).into())
+            }
+            State::Empty => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::{self, BufRead};
+use std::mem;
+
+use futures::{Poll, Future};
+
+use AsyncRead;
+
+/// A future which can be used to easily read the contents of a stream into a
+/// vector until any one of a set of delimiters is reached.
+///
+/// Created by the [`read_until_any`] function.
+///
+/// [`read_until_any`]: fn.read_until_any.html
+#[derive(Debug)]
+pub struct ReadUntilAny<A, T> {
+    state: State<A, T>,
+}
+
+#[derive(Debug)]
+enum State<A, T> {
+    Reading {
+        a: A,
+        delimiters: T,
+        buf: Vec<u8>,
+    },
+    Empty,
+}
+
+/// Creates a future which will read all the bytes associated with the I/O
+/// object `A` into the buffer provided until any of the bytes in `delimiters`
+/// is reached.
+///
+/// This behaves like [`read_until`], except that reading stops at the first
+/// byte that is contained in `delimiters`. The future resolves to the I/O
+/// object, the buffer with all bytes up to, and including, the delimiter and
+/// the delimiter which was matched. If EOF is hit before any delimiter is found
+/// the buffer holds everything that was read and `None` is returned in place of
+/// the delimiter.
+///
+/// An empty `delimiters` slice can never match and causes the future to
+/// resolve to an error of kind `InvalidInput`. In case of an error the buffer
+/// and the object will be discarded, with the error yielded.
+///
+/// [`read_until`]: fn.read_until.html
+pub fn read_until_any<A, T>(a: A, delimiters: T, buf: Vec<u8>) -> ReadUntilAny<A, T>
+    where A: AsyncRead + BufRead,
+          T: AsRef<[u8]>,
+{
+    ReadUntilAny {
+        state: State::Reading {
+            a: a,
+            delimiters: delimiters,
+            buf: buf,
+        }
+    }
+}
+
+fn read_until_any_internal<A: BufRead>(a: &mut A, delimiters: &[u8], buf: &mut Vec<u8>)
+    -> io::Result<Option<u8>>
+{
+    loop {
+        let (done, used, matched) = {
+            let available = match a.fill_buf() {
+                Ok(n) => n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            match available.iter().position(|b| delimiters.contains(b)) {
+                Some(i) => {
+                    buf.extend_from_slice(&available[..i + 1]);
+                    (true, i + 1, Some(available[i]))
+                }
+                None => {
+                    buf.extend_from_slice(available);
+                    (available.is_empty(), available.len(), None)
+                }
+            }
+        };
+        a.consume(used);
+        if done {
+            return Ok(matched);
+        }
+    }
+}
+
+impl<A, T> Future for ReadUntilAny<A, T>
+    where A: AsyncRead + BufRead,
+          T: AsRef<[u8]>,
+{
+    type Item = (A, Vec<u8>, Option<u8>);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, Vec<u8>, Option<u8>), io::Error> {
+        let matched = match self.state {
+            State::Reading { ref mut a, ref delimiters, ref mut buf } => {
+                let delimiters = delimiters.as_ref();
+                if delimiters.is_empty() {
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                              "no delimiters to read until"));
+                }
+                // Bytes are consumed from `a` as soon as they're appended to
+                // `buf`, so on "would block" all the data read so far is kept
+                // and reading resumes where it left off.
+                try_nb!(read_until_any_internal(a, delimiters, buf))
+            },
+            State::Empty => panic!("poll ReadUntilAny after it's done"),
+        };
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Reading { a, buf, .. } => Ok((a, buf, matched).into()),
+            State::Empty => unreachable!(),
+        }
+    }
+}
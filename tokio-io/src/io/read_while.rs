@@ -0,0 +1,103 @@
+use std::io::{self, BufRead};
+use std::{fmt, mem};
+
+use futures::{Poll, Future};
+
+use AsyncRead;
+
+/// A future which can be used to easily read the contents of a stream into a
+/// vector while its bytes match a predicate.
+///
+/// Created by the [`read_while`] function.
+///
+/// [`read_while`]: fn.read_while.html
+pub struct ReadWhile<A, F> {
+    state: State<A, F>,
+}
+
+enum State<A, F> {
+    Reading {
+        a: A,
+        predicate: F,
+        buf: Vec<u8>,
+    },
+    Empty,
+}
+
+/// Creates a future which will read the bytes associated with the I/O object
+/// `A` into the buffer provided for as long as `predicate` returns `true`.
+///
+/// Reading stops at the first byte for which `predicate` returns `false`,
+/// which is left unconsumed in the I/O object, or at EOF. This is the
+/// asynchronous equivalent of the `take_while` scanner found in parser
+/// combinator libraries, e.g. for reading a run of digits.
+///
+/// In case of an error the buffer and the object will be discarded, with the
+/// error yielded. In the case of success the object will be destroyed and
+/// the buffer will be returned, with all matching bytes appended to it.
+pub fn read_while<A, F>(a: A, predicate: F, buf: Vec<u8>) -> ReadWhile<A, F>
+    where A: AsyncRead + BufRead,
+          F: FnMut(u8) -> bool,
+{
+    ReadWhile {
+        state: State::Reading {
+            a: a,
+            predicate: predicate,
+            buf: buf,
+        }
+    }
+}
+
+impl<A, F> Future for ReadWhile<A, F>
+    where A: AsyncRead + BufRead,
+          F: FnMut(u8) -> bool,
+{
+    type Item = (A, Vec<u8>);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, Vec<u8>), io::Error> {
+        match self.state {
+            State::Reading { ref mut a, ref mut predicate, ref mut buf } => {
+                loop {
+                    let (done, used) = {
+                        let available = try_nb!(a.fill_buf());
+                        match available.iter().position(|b| !predicate(*b)) {
+                            Some(i) => {
+                                buf.extend_from_slice(&available[..i]);
+                                (true, i)
+                            }
+                            None => {
+                                buf.extend_from_slice(available);
+                                (available.is_empty(), available.len())
+                            }
+                        }
+                    };
+                    a.consume(used);
+                    if done {
+                        break;
+                    }
+                }
+            },
+            State::Empty => panic!("poll ReadWhile after it's done"),
+        }
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Reading { a, buf, .. } => Ok((a, buf).into()),
+            State::Empty => unreachable!(),
+        }
+    }
+}
+
+impl<A: fmt::Debug, F> fmt::Debug for ReadWhile<A, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.state {
+            State::Reading { ref a, ref buf, .. } => {
+                f.debug_struct("ReadWhile")
+                    .field("a", a)
+                    .field("buf", buf)
+                    .finish()
+            }
+            State::Empty => f.debug_struct("ReadWhile").finish(),
+        }
+    }
+}
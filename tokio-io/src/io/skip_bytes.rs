@@ -0,0 +1,80 @@
+use std::cmp;
+use std::io::{self, BufRead};
+use std::mem;
+
+use futures::{Poll, Future};
+
+use AsyncRead;
+
+/// A future which can be used to skip a fixed number of bytes of a stream.
+///
+/// Created by the [`skip_bytes`] function.
+///
+/// [`skip_bytes`]: fn.skip_bytes.html
+#[derive(Debug)]
+pub struct SkipBytes<A> {
+    state: State<A>,
+}
+
+#[derive(Debug)]
+enum State<A> {
+    Skipping {
+        a: A,
+        remaining: u64,
+    },
+    Empty,
+}
+
+/// Creates a future which will discard exactly `n` bytes of the I/O object
+/// `A`.
+///
+/// The bytes are consumed from the reader's buffer without being copied
+/// anywhere, so no scratch buffer is needed to skip over padding or reserved
+/// fields. The future resolves to the I/O object once all `n` bytes have been
+/// discarded.
+///
+/// If EOF is hit before `n` bytes were skipped, the future resolves to an
+/// error of kind `UnexpectedEof`. In case of an error the object will be
+/// discarded, with the error yielded.
+pub fn skip_bytes<A>(a: A, n: u64) -> SkipBytes<A>
+    where A: AsyncRead + BufRead,
+{
+    SkipBytes {
+        state: State::Skipping {
+            a: a,
+            remaining: n,
+        }
+    }
+}
+
+impl<A> Future for SkipBytes<A>
+    where A: AsyncRead + BufRead,
+{
+    type Item = A;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<A, io::Error> {
+        match self.state {
+            State::Skipping { ref mut a, ref mut remaining } => {
+                while *remaining > 0 {
+                    let n = {
+                        let available = try_nb!(a.fill_buf());
+                        if available.is_empty() {
+                            return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                                      "early eof"));
+                        }
+                        cmp::min(available.len() as u64, *remaining) as usize
+                    };
+                    a.consume(n);
+                    *remaining -= n as u64;
+                }
+            },
+            State::Empty => panic!("poll SkipBytes after it's done"),
+        }
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Skipping { a, .. } => Ok(a.into()),
+            State::Empty => unreachable!(),
+        }
+    }
+}
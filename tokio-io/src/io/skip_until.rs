@@ -0,0 +1,86 @@
+use std::io::{self, BufRead};
+use std::mem;
+
+use futures::{Poll, Future};
+
+use AsyncRead;
+
+/// A future which can be used to skip the contents of a stream up to a
+/// delimiter without keeping them.
+///
+/// Created by the [`skip_until`] function.
+///
+/// [`skip_until`]: fn.skip_until.html
+#[derive(Debug)]
+pub struct SkipUntil<A> {
+    state: State<A>,
+}
+
+#[derive(Debug)]
+enum State<A> {
+    Skipping {
+        a: A,
+        byte: u8,
+        skipped: usize,
+    },
+    Empty,
+}
+
+/// Creates a future which will discard all the bytes associated with the I/O
+/// object `A` until the delimiter `byte` is reached.
+///
+/// This behaves like [`read_until`], except that the bytes are consumed from
+/// the reader's buffer without being copied anywhere, which makes it suitable
+/// for skipping over data of arbitrary length, e.g. to synchronize to a known
+/// marker. The future resolves to the I/O object and the number of bytes
+/// skipped, including the delimiter. If EOF is hit before the delimiter is
+/// found, all remaining bytes are skipped.
+///
+/// In case of an error the object will be discarded, with the error yielded.
+///
+/// [`read_until`]: fn.read_until.html
+pub fn skip_until<A>(a: A, byte: u8) -> SkipUntil<A>
+    where A: AsyncRead + BufRead,
+{
+    SkipUntil {
+        state: State::Skipping {
+            a: a,
+            byte: byte,
+            skipped: 0,
+        }
+    }
+}
+
+impl<A> Future for SkipUntil<A>
+    where A: AsyncRead + BufRead,
+{
+    type Item = (A, usize);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, usize), io::Error> {
+        match self.state {
+            State::Skipping { ref mut a, byte, ref mut skipped } => {
+                loop {
+                    let (done, used) = {
+                        let available = try_nb!(a.fill_buf());
+                        match available.iter().position(|b| *b == byte) {
+                            Some(i) => (true, i + 1),
+                            None => (available.is_empty(), available.len()),
+                        }
+                    };
+                    a.consume(used);
+                    *skipped += used;
+                    if done {
+                        break;
+                    }
+                }
+            },
+            State::Empty => panic!("poll SkipUntil after it's done"),
+        }
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Skipping { a, skipped, .. } => Ok((a, skipped).into()),
+            State::Empty => unreachable!(),
+        }
+    }
+}
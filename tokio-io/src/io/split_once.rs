@@ -0,0 +1,96 @@
+use std::io::{self, BufRead};
+use std::mem;
+
+use futures::{Poll, Future};
+
+use AsyncRead;
+
+/// A future which reads the head of a stream up to a delimiter.
+///
+/// Created by the [`split_once`] function.
+///
+/// [`split_once`]: fn.split_once.html
+#[derive(Debug)]
+pub struct SplitOnce<A> {
+    state: State<A>,
+}
+
+#[derive(Debug)]
+enum State<A> {
+    Reading {
+        a: A,
+        byte: u8,
+        head: Vec<u8>,
+    },
+    Empty,
+}
+
+/// Creates a future which splits the I/O object `A` at the first occurrence of
+/// the delimiter `byte`.
+///
+/// The future resolves to the I/O object, positioned just after the
+/// delimiter, and all the data before the delimiter. This is useful for
+/// protocols which send a header terminated by a delimiter followed by a
+/// body, which can then be read from the returned object. Pass `&mut a` to
+/// keep ownership of the object.
+///
+/// Unlike [`read_until`] the delimiter is not included in the returned data.
+/// If EOF is hit before the delimiter is found, the future resolves to an
+/// error of kind `UnexpectedEof`. In case of an error the object will be
+/// discarded, with the error yielded.
+///
+/// [`read_until`]: fn.read_until.html
+pub fn split_once<A>(a: A, byte: u8) -> SplitOnce<A>
+    where A: AsyncRead + BufRead,
+{
+    SplitOnce {
+        state: State::Reading {
+            a: a,
+            byte: byte,
+            head: Vec::new(),
+        }
+    }
+}
+
+impl<A> Future for SplitOnce<A>
+    where A: AsyncRead + BufRead,
+{
+    type Item = (A, Vec<u8>);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, Vec<u8>), io::Error> {
+        match self.state {
+            State::Reading { ref mut a, byte, ref mut head } => {
+                loop {
+                    let (found, used) = {
+                        let available = try_nb!(a.fill_buf());
+                        if available.is_empty() {
+                            return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                                      "delimiter not found"));
+                        }
+                        match available.iter().position(|b| *b == byte) {
+                            Some(i) => {
+                                head.extend_from_slice(&available[..i]);
+                                (true, i + 1)
+                            }
+                            None => {
+                                head.extend_from_slice(available);
+                                (false, available.len())
+                            }
+                        }
+                    };
+                    a.consume(used);
+                    if found {
+                        break;
+                    }
+                }
+            },
+            State::Empty => panic!("poll SplitOnce after it's done"),
+        }
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Reading { a, head, .. } => Ok((a, head).into()),
+            State::Empty => unreachable!(),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use {codec, AsyncRead, AsyncWrite};
 
-use bytes::{Buf, BufMut, BytesMut, IntoBuf, BigEndian, LittleEndian};
+use bytes::{Buf, BufMut, Bytes, BytesMut, IntoBuf, BigEndian, LittleEndian};
 use bytes::buf::Chain;
 
 use futures::{Async, AsyncSink, Stream, Sink, StartSend, Poll};
@@ -53,7 +53,7 @@ pub struct Framed<T, B: IntoBuf = BytesMut> {
 /// [module level]: index.html
 #[derive(Debug)]
 pub struct FramedRead<T> {
-    inner: codec::FramedRead<T, Decoder>,
+    inner: codec::FramedRead<T, LengthDelimitedCodec>,
 }
 
 /// An error when the number of bytes read is more than max frame length.
@@ -61,13 +61,29 @@ pub struct FrameTooBig {
     _priv: (),
 }
 
-#[derive(Debug)]
-struct Decoder {
+/// A codec for frames delimited by a frame head specifying their lengths.
+///
+/// This allows the consumer to work with entire frames without having to
+/// worry about buffering or other framing logic. It can be used with any of
+/// the generic framing adapters, such as `codec::FramedRead`, in place of the
+/// length delimited specific `FramedRead`, `FramedWrite` and `Framed` types.
+///
+/// Decoded frames are yielded as `BytesMut` with the frame head stripped and
+/// frames are encoded from `Bytes`, prepending the frame head.
+///
+/// See [module level] documentation for more detail.
+///
+/// [module level]: index.html
+#[derive(Debug, Clone)]
+pub struct LengthDelimitedCodec {
     // Configuration values
     builder: Builder,
 
     // Read state
     state: DecodeState,
+
+    // Explicitly configured `Decoder::max_buf_size`
+    max_buf_size: Option<usize>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -271,9 +287,63 @@ impl<T: AsyncWrite> AsyncWrite for FramedRead<T> {
     }
 }
 
-// ===== impl Decoder ======
+// ===== impl LengthDelimitedCodec ======
+
+impl LengthDelimitedCodec {
+    /// Creates a new `LengthDelimitedCodec` with the default configuration
+    /// values.
+    pub fn new() -> LengthDelimitedCodec {
+        LengthDelimitedCodec::builder().new_codec()
+    }
+
+    /// Creates a new length delimited codec builder with default configuration
+    /// values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio_io::codec::length_delimited::LengthDelimitedCodec;
+    ///
+    /// let codec = LengthDelimitedCodec::builder()
+    ///     .length_field_length(2)
+    ///     .little_endian()
+    ///     .new_codec();
+    /// # let _ = codec;
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    /// Returns the current max frame setting
+    ///
+    /// This is the largest size this codec will accept from the wire and
+    /// write to the wire. Larger frames will be rejected.
+    pub fn max_frame_length(&self) -> usize {
+        self.builder.max_frame_len
+    }
+
+    /// Updates the max frame setting.
+    ///
+    /// The change takes effect the next time a frame is decoded or encoded. In
+    /// other words, if a frame is currently in process of being decoded with a
+    /// frame size greater than `val` but less than the max frame length in
+    /// effect before calling this function, then the frame will be allowed.
+    pub fn set_max_frame_length(&mut self, val: usize) {
+        self.builder.max_frame_length(val);
+    }
+
+    /// Sets the value returned by `Decoder::max_buf_size`.
+    ///
+    /// By default the limit is derived from the max frame length, so that a
+    /// single frame of the maximum size, including its frame head, always
+    /// fits into the buffer.
+    pub fn with_max_buf_size(self, max_buf_size: usize) -> LengthDelimitedCodec {
+        LengthDelimitedCodec {
+            max_buf_size: Some(max_buf_size),
+            ..self
+        }
+    }
 
-impl Decoder {
     fn decode_head(&mut self, src: &mut BytesMut) -> io::Result<Option<usize>> {
         let head_len = self.builder.num_head_bytes();
         let field_len = self.builder.length_field_len;
@@ -343,7 +413,7 @@ impl Decoder {
     }
 }
 
-impl codec::Decoder for Decoder {
+impl codec::Decoder for LengthDelimitedCodec {
     type Item = BytesMut;
     type Error = io::Error;
 
@@ -374,6 +444,27 @@ impl codec::Decoder for Decoder {
             None => Ok(None),
         }
     }
+
+    fn max_buf_size(&self) -> usize {
+        self.max_buf_size.unwrap_or_else(|| {
+            let adjustment = cmp::max(self.builder.length_adjustment, 0) as usize;
+            self.builder.max_frame_len
+                .saturating_add(adjustment)
+                .saturating_add(self.builder.num_head_bytes())
+        })
+    }
+}
+
+impl codec::Encoder for LengthDelimitedCodec {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn encode(&mut self, data: Bytes, dst: &mut BytesMut) -> io::Result<()> {
+        dst.reserve(self.builder.length_field_len + data.len());
+        try!(self.builder.encode_head(data.len(), dst));
+        dst.put(data);
+        Ok(())
+    }
 }
 
 // ===== impl FramedWrite =====
@@ -457,32 +548,7 @@ impl<T: AsyncWrite, B: IntoBuf> FramedWrite<T, B> {
 
     fn set_frame(&mut self, buf: B::Buf) -> io::Result<()> {
         let mut head = BytesMut::with_capacity(8);
-        let n = buf.remaining();
-
-        if n > self.builder.max_frame_len {
-            return Err(io::Error::new(io::ErrorKind::InvalidInput, FrameTooBig {
-                _priv: (),
-            }));
-        }
-
-        // Adjust `n` with bounds checking
-        let n = if self.builder.length_adjustment < 0 {
-            n.checked_add(-self.builder.length_adjustment as usize)
-        } else {
-            n.checked_sub(self.builder.length_adjustment as usize)
-        };
-
-        // Error handling
-        let n = match n {
-            Some(n) => n,
-            None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "provided length would overflow after adjustment")),
-        };
-
-        if self.builder.length_field_is_big_endian {
-            head.put_uint::<BigEndian>(n as u64, self.builder.length_field_len);
-        } else {
-            head.put_uint::<LittleEndian>(n as u64, self.builder.length_field_len);
-        }
+        try!(self.builder.encode_head(buf.remaining(), &mut head));
 
         debug_assert!(self.frame.is_none());
 
@@ -814,10 +880,28 @@ impl Builder {
         where T: AsyncRead,
     {
         FramedRead {
-            inner: codec::FramedRead::new(upstream, Decoder {
-                builder: *self,
-                state: DecodeState::Head,
-            }),
+            inner: codec::FramedRead::new(upstream, self.new_codec()),
+        }
+    }
+
+    /// Create a configured `LengthDelimitedCodec`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio_io::codec::length_delimited::Builder;
+    ///
+    /// let codec = Builder::new()
+    ///     .length_field_length(1)
+    ///     .max_frame_length(255)
+    ///     .new_codec();
+    /// # let _ = codec;
+    /// ```
+    pub fn new_codec(&self) -> LengthDelimitedCodec {
+        LengthDelimitedCodec {
+            builder: *self,
+            state: DecodeState::Head,
+            max_buf_size: None,
         }
     }
 
@@ -876,6 +960,36 @@ impl Builder {
         Framed { inner: inner }
     }
 
+    // Writes the frame head for a payload of `n` bytes into `dst`
+    fn encode_head(&self, n: usize, dst: &mut BytesMut) -> io::Result<()> {
+        if n > self.max_frame_len {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, FrameTooBig {
+                _priv: (),
+            }));
+        }
+
+        // Adjust `n` with bounds checking
+        let n = if self.length_adjustment < 0 {
+            n.checked_add(-self.length_adjustment as usize)
+        } else {
+            n.checked_sub(self.length_adjustment as usize)
+        };
+
+        // Error handling
+        let n = match n {
+            Some(n) => n,
+            None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "provided length would overflow after adjustment")),
+        };
+
+        if self.length_field_is_big_endian {
+            dst.put_uint::<BigEndian>(n as u64, self.length_field_len);
+        } else {
+            dst.put_uint::<LittleEndian>(n as u64, self.length_field_len);
+        }
+
+        Ok(())
+    }
+
     fn num_head_bytes(&self) -> usize {
         let num = self.length_field_offset + self.length_field_len;
         cmp::max(num, self.num_skip.unwrap_or(0))
@@ -15,6 +15,22 @@ extern crate log;
 #[macro_use]
 extern crate futures;
 extern crate bytes;
+#[cfg(feature = "bincode-codec")]
+extern crate bincode;
+#[cfg(feature = "crc32")]
+extern crate crc32fast;
+#[cfg(feature = "csv")]
+extern crate csv;
+#[cfg(feature = "digest")]
+extern crate digest;
+#[cfg(feature = "flate2")]
+extern crate flate2;
+#[cfg(feature = "prost")]
+extern crate prost;
+#[cfg(any(feature = "json", feature = "bincode-codec"))]
+extern crate serde;
+#[cfg(feature = "json")]
+extern crate serde_json;
 
 use std::io as std_io;
 
@@ -47,16 +63,48 @@ pub mod io;
 pub mod codec;
 
 mod allow_std;
+mod async_buf_write;
 mod async_read;
 mod async_write;
+mod blocking;
+mod buf_reader;
+mod buf_stream;
+mod buf_writer;
+mod count_bytes;
+mod count_write;
+#[cfg(feature = "crc32")]
+mod crc_read;
+mod duplex;
+mod empty;
 mod framed;
+mod framed_buf_read;
 mod framed_read;
 mod framed_write;
+#[cfg(feature = "digest")]
+mod hash_read;
+mod inspect;
 mod length_delimited;
+mod limited_read;
+mod limited_write;
 mod lines;
+mod merge;
+mod pipe;
+mod read_buf;
+mod repeat;
+mod scan;
 mod split;
+mod split_at_limit;
+mod split_str;
+mod stdio;
+mod stream_reader;
+mod tee;
+mod timeout;
 mod window;
+mod windows;
+mod writer;
+mod zero;
 
+pub use self::async_buf_write::AsyncBufWrite;
 pub use self::async_read::AsyncRead;
 pub use self::async_write::AsyncWrite;
 
@@ -64,4 +112,5 @@ fn _assert_objects() {
     fn _assert<T>() {}
     _assert::<Box<AsyncRead>>();
     _assert::<Box<AsyncWrite>>();
+    _assert::<Box<AsyncBufWrite>>();
 }
@@ -0,0 +1,98 @@
+use std::cmp;
+use std::io::{self, BufRead, Read, Write};
+
+use futures::Poll;
+
+use {AsyncRead, AsyncWrite};
+
+/// Wraps a reader and limits the amount of data returned by a single read.
+///
+/// Every call to `read` returns at most `chunk_size` bytes, and if the
+/// reader also implements `BufRead`, `fill_buf` returns at most `chunk_size`
+/// bytes as well. This is mostly useful in tests, to deterministically
+/// simulate data arriving in small fragments, as it would over a slow
+/// network.
+#[derive(Debug)]
+pub struct LimitedRead<R> {
+    inner: R,
+    chunk_size: usize,
+}
+
+impl<R: AsyncRead> LimitedRead<R> {
+    /// Creates a new `LimitedRead` returning at most `chunk_size` bytes from
+    /// `inner` per read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0, since reads couldn't make any progress.
+    pub fn new(inner: R, chunk_size: usize) -> LimitedRead<R> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        LimitedRead {
+            inner: inner,
+            chunk_size: chunk_size,
+        }
+    }
+}
+
+impl<R> LimitedRead<R> {
+    /// Returns the maximum number of bytes returned by a single read.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the `LimitedRead`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> Read for LimitedRead<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = cmp::min(buf.len(), self.chunk_size);
+        self.inner.read(&mut buf[..len])
+    }
+}
+
+impl<R: AsyncRead + BufRead> BufRead for LimitedRead<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let buf = try!(self.inner.fill_buf());
+        let len = cmp::min(buf.len(), self.chunk_size);
+        Ok(&buf[..len])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for LimitedRead<R> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<R: Write> Write for LimitedRead<R> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<R: AsyncWrite> AsyncWrite for LimitedRead<R> {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.inner.shutdown()
+    }
+}
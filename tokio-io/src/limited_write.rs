@@ -0,0 +1,114 @@
+use std::cmp;
+use std::io::{self, BufRead, Read, Write};
+
+use futures::Poll;
+
+use {AsyncRead, AsyncWrite};
+
+/// Wraps a writer and fails writes once a total number of bytes has been
+/// written.
+///
+/// Writes are passed through to the underlying writer until `limit` bytes
+/// have been written in total; a write crossing the limit is shortened to
+/// end exactly at it. After that every non-empty write fails, by default
+/// with an error of kind `WriteZero`. This is mostly useful in tests, to
+/// simulate a full disk or a connection closed by the peer and check how
+/// partial writes are handled.
+#[derive(Debug)]
+pub struct LimitedWrite<W> {
+    inner: W,
+    limit: u64,
+    written: u64,
+    error_kind: io::ErrorKind,
+}
+
+impl<W: AsyncWrite> LimitedWrite<W> {
+    /// Creates a new `LimitedWrite` accepting at most `limit` bytes for
+    /// `inner`.
+    pub fn new(inner: W, limit: u64) -> LimitedWrite<W> {
+        LimitedWrite {
+            inner: inner,
+            limit: limit,
+            written: 0,
+            error_kind: io::ErrorKind::WriteZero,
+        }
+    }
+}
+
+impl<W> LimitedWrite<W> {
+    /// Returns the number of bytes written so far.
+    pub fn bytes_written(&self) -> u64 {
+        self.written
+    }
+
+    /// Returns the total number of bytes which may be written.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Sets the kind of the error returned by writes once the limit has been
+    /// reached.
+    pub fn set_error_kind(&mut self, kind: io::ErrorKind) {
+        self.error_kind = kind;
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes the `LimitedWrite`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> Write for LimitedWrite<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let remaining = self.limit - self.written;
+        if remaining == 0 && !buf.is_empty() {
+            return Err(io::Error::new(self.error_kind, "write limit reached"));
+        }
+        let len = cmp::min(buf.len() as u64, remaining) as usize;
+        let n = try!(self.inner.write(&buf[..len]));
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for LimitedWrite<W> {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.inner.shutdown()
+    }
+}
+
+impl<W: Read> Read for LimitedWrite<W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<W: BufRead> BufRead for LimitedWrite<W> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+impl<W: AsyncRead> AsyncRead for LimitedWrite<W> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
@@ -1,5 +1,8 @@
+use std::fmt;
 use std::io::{self, BufRead};
+use std::marker::PhantomData;
 use std::mem;
+use std::str::FromStr;
 
 use futures::{Poll, Stream};
 
@@ -58,3 +61,120 @@ impl<A> Stream for Lines<A>
         Ok(Some(mem::replace(&mut self.line, String::new())).into())
     }
 }
+
+/// Combinator created by the top-level `lines_bytes` method which is a stream
+/// over the lines of bytes on an I/O object.
+#[derive(Debug)]
+pub struct LinesBytes<A> {
+    io: A,
+    line: Vec<u8>,
+}
+
+/// Creates a new stream from the I/O object given representing the lines of
+/// input that are found on `A`, as raw bytes.
+///
+/// This behaves like `lines`, stripping the trailing `\n` or `\r\n` of each
+/// line, except that lines aren't required to be valid UTF-8. It's meant for
+/// binary protocols which use newline framing, and avoids the cost of
+/// validating lines which are never used as text.
+pub fn lines_bytes<A>(a: A) -> LinesBytes<A>
+    where A: AsyncRead + BufRead,
+{
+    LinesBytes {
+        io: a,
+        line: Vec::new(),
+    }
+}
+
+impl<A> LinesBytes<A> {
+    /// Returns the underlying I/O object.
+    ///
+    /// Note that this may lose data already read into internal buffers. It's
+    /// recommended to only call this once the stream has reached its end.
+    pub fn into_inner(self) -> A {
+        self.io
+    }
+}
+
+impl<A> Stream for LinesBytes<A>
+    where A: AsyncRead + BufRead,
+{
+    type Item = Vec<u8>;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<Vec<u8>>, io::Error> {
+        let n = try_nb!(self.io.read_until(b'\n', &mut self.line));
+        if n == 0 && self.line.len() == 0 {
+            return Ok(None.into())
+        }
+        if self.line.ends_with(b"\n") {
+            self.line.pop();
+            if self.line.ends_with(b"\r") {
+                self.line.pop();
+            }
+        }
+        Ok(Some(mem::replace(&mut self.line, Vec::new())).into())
+    }
+}
+
+/// Combinator created by the top-level `parse_lines` method which is a stream
+/// over the values parsed from the lines of an I/O object.
+pub struct ParseLines<A, T> {
+    lines: Lines<A>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Creates a new stream which parses every line of input found on `A` into a
+/// `T`.
+///
+/// This behaves like `lines`, except that each line is converted with
+/// `FromStr`. A line which fails to parse results in an error of kind
+/// `InvalidData`, carrying the parse error as a string.
+pub fn parse_lines<A, T>(a: A) -> ParseLines<A, T>
+    where A: AsyncRead + BufRead,
+          T: FromStr,
+          T::Err: fmt::Display,
+{
+    ParseLines {
+        lines: lines(a),
+        _marker: PhantomData,
+    }
+}
+
+impl<A, T> ParseLines<A, T> {
+    /// Returns the underlying I/O object.
+    ///
+    /// Note that this may lose data already read into internal buffers. It's
+    /// recommended to only call this once the stream has reached its end.
+    pub fn into_inner(self) -> A {
+        self.lines.into_inner()
+    }
+}
+
+impl<A, T> Stream for ParseLines<A, T>
+    where A: AsyncRead + BufRead,
+          T: FromStr,
+          T::Err: fmt::Display,
+{
+    type Item = T;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<T>, io::Error> {
+        let line = match try_ready!(self.lines.poll()) {
+            Some(line) => line,
+            None => return Ok(None.into()),
+        };
+        match line.parse() {
+            Ok(value) => Ok(Some(value).into()),
+            Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, e.to_string())),
+        }
+    }
+}
+
+impl<A: fmt::Debug, T> fmt::Debug for ParseLines<A, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ParseLines")
+            .field("lines", &self.lines)
+            .finish()
+    }
+}
@@ -0,0 +1,111 @@
+use std::io::{self, BufRead};
+
+use bytes::Bytes;
+use futures::{Async, Poll, Stream};
+
+use AsyncRead;
+
+/// Combinator created by the top-level `merge` method which is a stream over
+/// the data of two I/O objects, in the order it becomes available.
+#[derive(Debug)]
+pub struct Merge<A, B> {
+    a: A,
+    b: B,
+    a_done: bool,
+    b_done: bool,
+    prefer_b: bool,
+}
+
+/// Creates a new stream yielding the data read from both `a` and `b`.
+///
+/// Every item is the data which was buffered by one of the readers when the
+/// stream was polled, so the stream yields data from whichever reader is
+/// ready first. If both are ready, `a` is read from first; use
+/// [`Merge::prefer_second`] to change that. Once one reader hits EOF the
+/// stream continues with the other one, and it ends once both have hit EOF.
+///
+/// This is the I/O counterpart of `Stream::select`. Note that the data of the
+/// two readers is interleaved at arbitrary points, so this is mostly useful
+/// when each read yields a complete message, or when the order doesn't
+/// matter, e.g. when merging log output.
+///
+/// [`Merge::prefer_second`]: struct.Merge.html#method.prefer_second
+pub fn merge<A, B>(a: A, b: B) -> Merge<A, B>
+    where A: AsyncRead + BufRead,
+          B: AsyncRead + BufRead,
+{
+    Merge {
+        a: a,
+        b: b,
+        a_done: false,
+        b_done: false,
+        prefer_b: false,
+    }
+}
+
+impl<A, B> Merge<A, B> {
+    /// Makes the stream read from the second reader first when both readers
+    /// are ready.
+    pub fn prefer_second(mut self) -> Merge<A, B> {
+        self.prefer_b = true;
+        self
+    }
+
+    /// Returns the underlying I/O objects.
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+// Takes the buffered data of `io`, returning `None` if it isn't ready and an
+// empty chunk at EOF.
+fn poll_chunk<R: BufRead>(io: &mut R) -> Poll<Bytes, io::Error> {
+    let chunk = {
+        let available = try_nb!(io.fill_buf());
+        Bytes::from(available)
+    };
+    io.consume(chunk.len());
+    Ok(Async::Ready(chunk))
+}
+
+impl<A, B> Stream for Merge<A, B>
+    where A: AsyncRead + BufRead,
+          B: AsyncRead + BufRead,
+{
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<Bytes>, io::Error> {
+        for i in 0..2 {
+            let from_b = (i == 0) == self.prefer_b;
+            let chunk = if from_b {
+                if self.b_done {
+                    continue;
+                }
+                poll_chunk(&mut self.b)
+            } else {
+                if self.a_done {
+                    continue;
+                }
+                poll_chunk(&mut self.a)
+            };
+            match try!(chunk) {
+                Async::Ready(ref chunk) if chunk.is_empty() => {
+                    if from_b {
+                        self.b_done = true;
+                    } else {
+                        self.a_done = true;
+                    }
+                }
+                Async::Ready(chunk) => return Ok(Async::Ready(Some(chunk))),
+                Async::NotReady => {}
+            }
+        }
+
+        if self.a_done && self.b_done {
+            Ok(Async::Ready(None))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
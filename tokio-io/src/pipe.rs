@@ -0,0 +1,206 @@
+use std::cmp;
+use std::io::{self, BufRead, Read, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use bytes::BytesMut;
+use futures::Poll;
+use futures::task::{self, Task};
+
+use {AsyncRead, AsyncWrite};
+
+/// The reading end of an in-memory pipe.
+///
+/// Created by the [`pipe`] function.
+///
+/// [`pipe`]: fn.pipe.html
+#[derive(Debug)]
+pub struct PipeReader {
+    pipe: Arc<Mutex<Pipe>>,
+    // Data taken out of the pipe to be handed out through `BufRead`
+    buf: BytesMut,
+}
+
+/// The writing end of an in-memory pipe.
+///
+/// Created by the [`pipe`] function.
+///
+/// [`pipe`]: fn.pipe.html
+#[derive(Debug)]
+pub struct PipeWriter {
+    pipe: Arc<Mutex<Pipe>>,
+}
+
+// The state shared by both ends of a pipe.
+#[derive(Debug)]
+struct Pipe {
+    buf: BytesMut,
+    capacity: usize,
+    is_closed: bool,
+    read_task: Option<Task>,
+    write_task: Option<Task>,
+}
+
+/// Creates an in-memory pipe, returning its writing and reading ends.
+///
+/// Data written to the `PipeWriter` can be read from the `PipeReader`. At
+/// most `capacity` bytes can be written before they're read; further writes
+/// block until there is room again. Reading through `BufRead` may take up to
+/// another `capacity` bytes out of the pipe into the `PipeReader`.
+///
+/// Shutting down or dropping the `PipeWriter` signals EOF to the reader once
+/// all data written before has been read. Writing after the `PipeReader` has
+/// been dropped fails with an error of kind `BrokenPipe`.
+///
+/// Like any other asynchronous I/O object both ends must be used from within
+/// a task.
+///
+/// A small capacity makes this useful for testing codecs: wrapping the ends
+/// in a `FramedWrite` and a `FramedRead` exercises how frames are decoded
+/// when they arrive in pieces, with backpressure on the writing side.
+///
+/// # Panics
+///
+/// Panics if `capacity` is 0.
+pub fn pipe(capacity: usize) -> (PipeWriter, PipeReader) {
+    assert!(capacity != 0, "pipe capacity must be non-zero");
+    let pipe = Arc::new(Mutex::new(Pipe {
+        buf: BytesMut::with_capacity(capacity),
+        capacity: capacity,
+        is_closed: false,
+        read_task: None,
+        write_task: None,
+    }));
+    let writer = PipeWriter { pipe: pipe.clone() };
+    let reader = PipeReader { pipe: pipe, buf: BytesMut::new() };
+    (writer, reader)
+}
+
+impl PipeReader {
+    /// Returns the number of bytes which can be read without blocking.
+    ///
+    /// This includes both data taken out of the pipe through `BufRead` and
+    /// data still waiting in the pipe.
+    pub fn buffered_bytes(&self) -> usize {
+        self.buf.len() + lock(&self.pipe).buf.len()
+    }
+}
+
+impl Pipe {
+    fn close(&mut self) {
+        self.is_closed = true;
+        if let Some(task) = self.read_task.take() {
+            task.notify();
+        }
+        if let Some(task) = self.write_task.take() {
+            task.notify();
+        }
+    }
+
+    // Takes up to `max` bytes out of the pipe, returning `None` if the read
+    // would block.
+    fn take(&mut self, max: usize) -> Option<BytesMut> {
+        if self.buf.is_empty() && !self.is_closed {
+            self.read_task = Some(task::current());
+            return None;
+        }
+        let n = cmp::min(max, self.buf.len());
+        if let Some(task) = self.write_task.take() {
+            task.notify();
+        }
+        Some(self.buf.split_to(n))
+    }
+}
+
+fn lock<'a>(pipe: &'a Mutex<Pipe>) -> MutexGuard<'a, Pipe> {
+    match pipe.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+fn would_block() -> io::Error {
+    io::Error::new(io::ErrorKind::WouldBlock, "would block")
+}
+
+impl Read for PipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.buf.is_empty() {
+            let data = match lock(&self.pipe).take(buf.len()) {
+                Some(data) => data,
+                None => return Err(would_block()),
+            };
+            buf[..data.len()].copy_from_slice(&data);
+            return Ok(data.len());
+        }
+        let n = cmp::min(buf.len(), self.buf.len());
+        buf[..n].copy_from_slice(&self.buf.split_to(n));
+        Ok(n)
+    }
+}
+
+impl BufRead for PipeReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.buf.is_empty() {
+            let mut pipe = lock(&self.pipe);
+            let max = pipe.capacity;
+            match pipe.take(max) {
+                Some(data) => self.buf = data,
+                None => return Err(would_block()),
+            }
+        }
+        Ok(&self.buf)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = cmp::min(amt, self.buf.len());
+        let _ = self.buf.split_to(amt);
+    }
+}
+
+impl AsyncRead for PipeReader {
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+}
+
+impl Drop for PipeReader {
+    fn drop(&mut self) {
+        lock(&self.pipe).close();
+    }
+}
+
+impl Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut pipe = lock(&self.pipe);
+        if pipe.is_closed {
+            return Err(io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed"));
+        }
+        let n = cmp::min(buf.len(), pipe.capacity - pipe.buf.len());
+        if n == 0 && !buf.is_empty() {
+            pipe.write_task = Some(task::current());
+            return Err(would_block());
+        }
+        pipe.buf.extend_from_slice(&buf[..n]);
+        if let Some(task) = pipe.read_task.take() {
+            task.notify();
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for PipeWriter {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        lock(&self.pipe).close();
+        Ok(().into())
+    }
+}
+
+impl Drop for PipeWriter {
+    fn drop(&mut self) {
+        lock(&self.pipe).close();
+    }
+}
@@ -0,0 +1,146 @@
+use std::{cmp, fmt};
+
+/// A buffer which is filled by reads and keeps track of how much of it has
+/// been initialized.
+///
+/// A `ReadBuf` wraps a slice of possibly uninitialized bytes, such as the
+/// one returned by `BufMut::bytes_mut`, and splits it
+/// into three regions: the *filled* part holding the data read so far,
+/// followed by a part which is initialized but unfilled, followed by memory
+/// which may be uninitialized. Reading through [`AsyncRead::poll_read_buf`]
+/// only initializes the uninitialized part once, so reusing the same buffer
+/// for many reads doesn't zero it out over and over again.
+///
+/// [`AsyncRead::poll_read_buf`]: ../trait.AsyncRead.html#method.poll_read_buf
+pub struct ReadBuf<'a> {
+    buf: &'a mut [u8],
+    filled: usize,
+    initialized: usize,
+}
+
+impl<'a> ReadBuf<'a> {
+    /// Creates a new `ReadBuf` from a fully initialized buffer.
+    pub fn new(buf: &'a mut [u8]) -> ReadBuf<'a> {
+        let initialized = buf.len();
+        ReadBuf {
+            buf: buf,
+            filled: 0,
+            initialized: initialized,
+        }
+    }
+
+    /// Creates a new `ReadBuf` from a buffer which may not be initialized.
+    ///
+    /// None of the contents of `buf` are handed out until they've been
+    /// initialized, either by a read or by zeroing them.
+    pub fn uninit(buf: &'a mut [u8]) -> ReadBuf<'a> {
+        ReadBuf {
+            buf: buf,
+            filled: 0,
+            initialized: 0,
+        }
+    }
+
+    /// Returns the total length of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns the filled part of the buffer.
+    pub fn filled(&self) -> &[u8] {
+        &self.buf[..self.filled]
+    }
+
+    /// Returns a mutable reference to the filled part of the buffer.
+    pub fn filled_mut(&mut self) -> &mut [u8] {
+        &mut self.buf[..self.filled]
+    }
+
+    /// Returns the length of the initialized part of the buffer, which
+    /// includes the filled part.
+    pub fn initialized_len(&self) -> usize {
+        self.initialized
+    }
+
+    /// Returns the number of bytes which can still be read into the buffer.
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.filled
+    }
+
+    /// Clears the filled part of the buffer.
+    ///
+    /// The memory stays initialized, so it's not prepared again by the next
+    /// read.
+    pub fn clear(&mut self) {
+        self.filled = 0;
+    }
+
+    /// Appends `data` to the filled part of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` is longer than `remaining()`.
+    pub fn put_slice(&mut self, data: &[u8]) {
+        assert!(data.len() <= self.remaining(), "data does not fit into ReadBuf");
+        let end = self.filled + data.len();
+        self.buf[self.filled..end].copy_from_slice(data);
+        self.filled = end;
+        self.initialized = cmp::max(self.initialized, end);
+    }
+
+    /// Marks the next `n` initialized bytes as filled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this would extend the filled part past the initialized
+    /// part of the buffer.
+    pub fn add_filled(&mut self, n: usize) {
+        let end = self.filled + n;
+        assert!(end <= self.initialized, "filled past initialized part of ReadBuf");
+        self.filled = end;
+    }
+
+    /// Zeroes the uninitialized part of the buffer, if any, and returns the
+    /// unfilled part as initialized memory.
+    ///
+    /// Memory is only zeroed the first time, since it's tracked as
+    /// initialized afterwards.
+    pub fn initialize_unfilled(&mut self) -> &mut [u8] {
+        for b in &mut self.buf[self.initialized..] {
+            *b = 0;
+        }
+        self.initialized = self.buf.len();
+        &mut self.buf[self.filled..]
+    }
+
+    /// Returns the unfilled part of the buffer.
+    ///
+    /// # Safety
+    ///
+    /// The part of the buffer past `initialized_len()` may be uninitialized,
+    /// so the caller must not read from it before writing to it.
+    pub unsafe fn unfilled_mut(&mut self) -> &mut [u8] {
+        &mut self.buf[self.filled..]
+    }
+
+    /// Marks the next `n` unfilled bytes as initialized.
+    ///
+    /// # Safety
+    ///
+    /// The caller must have initialized those bytes, e.g. by reading into
+    /// them.
+    pub unsafe fn assume_init(&mut self, n: usize) {
+        let end = cmp::min(self.filled + n, self.buf.len());
+        self.initialized = cmp::max(self.initialized, end);
+    }
+}
+
+impl<'a> fmt::Debug for ReadBuf<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ReadBuf")
+            .field("filled", &self.filled)
+            .field("initialized", &self.initialized)
+            .field("capacity", &self.buf.len())
+            .finish()
+    }
+}
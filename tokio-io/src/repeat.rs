@@ -0,0 +1,63 @@
+use std::{fmt, io};
+
+use AsyncRead;
+
+const BUF_LEN: usize = 256;
+
+/// A reader which yields one byte over and over.
+///
+/// Unlike `std::io::Repeat` this also implements `BufRead`, so it can be used
+/// with the buffered helpers such as [`read_until`].
+///
+/// Created by the [`repeat`] function.
+///
+/// [`read_until`]: fn.read_until.html
+/// [`repeat`]: fn.repeat.html
+#[derive(Clone)]
+pub struct Repeat {
+    buf: [u8; BUF_LEN],
+}
+
+/// Creates an instance of a reader that infinitely repeats one byte.
+///
+/// All reads from this reader will succeed immediately by filling the
+/// provided buffer with the given byte, and the buffered contents are always
+/// a non-empty run of that byte. This is mostly useful as a never-ending data
+/// source in tests.
+pub fn repeat(byte: u8) -> Repeat {
+    Repeat {
+        buf: [byte; BUF_LEN],
+    }
+}
+
+impl io::Read for Repeat {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        for slot in &mut *buf {
+            *slot = self.buf[0];
+        }
+        Ok(buf.len())
+    }
+}
+
+impl io::BufRead for Repeat {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(&self.buf)
+    }
+
+    fn consume(&mut self, _amt: usize) {
+    }
+}
+
+impl AsyncRead for Repeat {
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+}
+
+impl fmt::Debug for Repeat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Repeat")
+            .field("byte", &self.buf[0])
+            .finish()
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+use std::io::{self, BufRead};
+
+use futures::{Async, Poll, Stream};
+
+use AsyncRead;
+
+/// Combinator created by the top-level `scan` method which is a stream of
+/// the items parsed from an I/O object by a stateful closure.
+pub struct Scan<A, S, F> {
+    io: A,
+    state: S,
+    f: F,
+}
+
+/// Creates a new stream from the I/O object given which feeds its input
+/// through `f`, yielding the items it produces.
+///
+/// This is the buffered I/O counterpart of `Iterator::scan`. Every chunk of
+/// data returned by `fill_buf` is passed to `f` along with the state, which
+/// starts out as `init`. The chunk is consumed in its entirety, so `f`
+/// should fold whatever it needs to keep of it into the state. It returns
+/// `Some` once an item is complete, which is then yielded by the stream, or
+/// `None` to be called again with more data. The stream ends once the I/O
+/// object reaches EOF; anything left in the state can be retrieved with
+/// `into_inner`.
+pub fn scan<A, S, F, T>(a: A, init: S, f: F) -> Scan<A, S, F>
+    where A: AsyncRead + BufRead,
+          F: FnMut(&mut S, &[u8]) -> Option<T>,
+{
+    Scan {
+        io: a,
+        state: init,
+        f: f,
+    }
+}
+
+impl<A, S, F> Scan<A, S, F> {
+    /// Returns a reference to the current state.
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Returns a mutable reference to the current state.
+    pub fn state_mut(&mut self) -> &mut S {
+        &mut self.state
+    }
+
+    /// Consumes the `Scan`, returning the underlying I/O object and the
+    /// current state.
+    pub fn into_inner(self) -> (A, S) {
+        (self.io, self.state)
+    }
+}
+
+impl<A, S, F, T> Stream for Scan<A, S, F>
+    where A: AsyncRead + BufRead,
+          F: FnMut(&mut S, &[u8]) -> Option<T>,
+{
+    type Item = T;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<T>, io::Error> {
+        loop {
+            let (n, item) = {
+                let available = try_nb!(self.io.fill_buf());
+                if available.is_empty() {
+                    return Ok(Async::Ready(None));
+                }
+                (available.len(), (self.f)(&mut self.state, available))
+            };
+            self.io.consume(n);
+            if let Some(item) = item {
+                return Ok(Async::Ready(Some(item)));
+            }
+        }
+    }
+}
+
+impl<A: fmt::Debug, S: fmt::Debug, F> fmt::Debug for Scan<A, S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Scan")
+            .field("io", &self.io)
+            .field("state", &self.state)
+            .finish()
+    }
+}
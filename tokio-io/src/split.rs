@@ -18,11 +18,38 @@ pub struct WriteHalf<T> {
     handle: BiLock<T>,
 }
 
+/// Splits a read/write object into a readable and a writable half.
+///
+/// This is the same as `AsyncRead::split`. The halves can be moved to
+/// different tasks; each access locks the shared object, so an operation on
+/// one half blocks while the other half is in the middle of one. The halves
+/// can be put back together with [`ReadHalf::unsplit`].
+///
+/// `ReadHalf` doesn't implement `BufRead`, since the buffer couldn't be
+/// borrowed past the lock. To read from it with the buffered combinators,
+/// wrap it in a `BufReader`.
+///
+/// [`ReadHalf::unsplit`]: struct.ReadHalf.html#method.unsplit
 pub fn split<T: AsyncRead + AsyncWrite>(t: T) -> (ReadHalf<T>, WriteHalf<T>) {
     let (a, b) = BiLock::new(t);
     (ReadHalf { handle: a }, WriteHalf { handle: b })
 }
 
+impl<T> ReadHalf<T> {
+    /// Reunites this half with the `WriteHalf` it was split from, returning
+    /// the original object.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` wasn't created by the same call to `split`.
+    pub fn unsplit(self, other: WriteHalf<T>) -> T {
+        match self.handle.reunite(other.handle) {
+            Ok(t) => t,
+            Err(_) => panic!("unrelated ReadHalf and WriteHalf passed to unsplit"),
+        }
+    }
+}
+
 fn would_block() -> io::Error {
     io::Error::new(io::ErrorKind::WouldBlock, "would block")
 }
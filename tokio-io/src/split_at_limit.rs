@@ -0,0 +1,170 @@
+use std::{cmp, fmt};
+use std::io::{self, Read};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use futures::task::{self, Task};
+
+use AsyncRead;
+
+/// The first part of a reader split by [`split_at_limit`], reading at most a
+/// fixed number of bytes.
+///
+/// [`split_at_limit`]: fn.split_at_limit.html
+pub struct Take<R> {
+    shared: Arc<Mutex<Shared<R>>>,
+}
+
+/// The rest of a reader split by [`split_at_limit`], readable once the
+/// [`Take`] it was split from is done.
+///
+/// [`split_at_limit`]: fn.split_at_limit.html
+/// [`Take`]: struct.Take.html
+pub struct Remainder<R> {
+    shared: Arc<Mutex<Shared<R>>>,
+}
+
+struct Shared<R> {
+    reader: R,
+    // Bytes left to be read through the `Take`.
+    remaining: u64,
+    take_dropped: bool,
+    // The task of a `Remainder` waiting for the `Take` to be done.
+    blocked: Option<Task>,
+}
+
+/// Splits `reader` into a reader of its next `n` bytes, and a reader of
+/// everything after them.
+///
+/// The [`Take`] reads at most `n` bytes from `reader`, like `Read::take`.
+/// Reads from the [`Remainder`] return `WouldBlock` until all of those bytes
+/// have been read, after which they continue right where the `Take` left
+/// off. If the `Take` is dropped early, the bytes it didn't read are skipped
+/// by the `Remainder`. This allows handing out a length-prefixed section of
+/// a stream, e.g. a message body, while the rest of the stream is processed
+/// by someone else once the section is done. If `reader` hits EOF before
+/// `n` bytes were read, the `Take` is done there as well.
+///
+/// Like the halves of [`split`], neither part implements `BufRead`, since
+/// the buffer couldn't be borrowed past the lock guarding the reader.
+///
+/// [`Take`]: struct.Take.html
+/// [`Remainder`]: struct.Remainder.html
+/// [`split`]: fn.split.html
+pub fn split_at_limit<R>(reader: R, n: u64) -> (Take<R>, Remainder<R>)
+    where R: AsyncRead,
+{
+    let shared = Arc::new(Mutex::new(Shared {
+        reader: reader,
+        remaining: n,
+        take_dropped: false,
+        blocked: None,
+    }));
+    (Take { shared: shared.clone() }, Remainder { shared: shared })
+}
+
+fn lock<'a, R>(shared: &'a Mutex<Shared<R>>) -> MutexGuard<'a, Shared<R>> {
+    match shared.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+impl<R> Shared<R> {
+    fn unblock(&mut self) {
+        if let Some(task) = self.blocked.take() {
+            task.notify();
+        }
+    }
+}
+
+impl<R> Take<R> {
+    /// Returns the number of bytes that can still be read through this
+    /// `Take`.
+    pub fn limit(&self) -> u64 {
+        lock(&self.shared).remaining
+    }
+}
+
+impl<R: AsyncRead> Read for Take<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut shared = lock(&self.shared);
+        if shared.remaining == 0 {
+            return Ok(0);
+        }
+        let max = cmp::min(buf.len() as u64, shared.remaining) as usize;
+        let n = try!(shared.reader.read(&mut buf[..max]));
+        if n == 0 && max > 0 {
+            // The reader hit EOF before the limit, so there's nothing left
+            // for the `Remainder` to wait for.
+            shared.remaining = 0;
+        } else {
+            shared.remaining -= n as u64;
+        }
+        if shared.remaining == 0 {
+            shared.unblock();
+        }
+        Ok(n)
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for Take<R> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        lock(&self.shared).reader.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<R> Drop for Take<R> {
+    fn drop(&mut self) {
+        let mut shared = lock(&self.shared);
+        shared.take_dropped = true;
+        shared.unblock();
+    }
+}
+
+impl<R: AsyncRead> Read for Remainder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut shared = lock(&self.shared);
+        if shared.remaining > 0 {
+            if !shared.take_dropped {
+                shared.blocked = Some(task::current());
+                return Err(io::Error::new(io::ErrorKind::WouldBlock, "would block"));
+            }
+            let mut skipped = [0; 1024];
+            while shared.remaining > 0 {
+                let max = cmp::min(skipped.len() as u64, shared.remaining) as usize;
+                let n = try!(shared.reader.read(&mut skipped[..max]));
+                if n == 0 {
+                    return Ok(0);
+                }
+                shared.remaining -= n as u64;
+            }
+        }
+        shared.reader.read(buf)
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for Remainder<R> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        lock(&self.shared).reader.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for Take<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let shared = lock(&self.shared);
+        f.debug_struct("Take")
+            .field("reader", &shared.reader)
+            .field("limit", &shared.remaining)
+            .finish()
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for Remainder<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let shared = lock(&self.shared);
+        f.debug_struct("Remainder")
+            .field("reader", &shared.reader)
+            .field("pending", &shared.remaining)
+            .finish()
+    }
+}
@@ -0,0 +1,97 @@
+use std::io::{self, BufRead};
+use std::mem;
+
+use futures::{Poll, Stream};
+
+use AsyncRead;
+
+/// Combinator created by the top-level `split_str` method which is a stream
+/// over the segments of an I/O object separated by a delimiter.
+#[derive(Debug)]
+pub struct SplitStr<A, T> {
+    io: A,
+    delimiter: T,
+    buf: Vec<u8>,
+}
+
+/// Creates a new stream from the I/O object given representing the segments
+/// of input that are separated by the byte sequence `delimiter`.
+///
+/// This method takes an asynchronous I/O object, `a`, and returns a `Stream`
+/// of byte vectors that the object contains, with the delimiter removed. A
+/// delimiter which is split across several reads from `a` is still
+/// recognized. The returned stream will reach its end once `a` reaches EOF,
+/// yielding any trailing bytes after the last delimiter as a final segment.
+///
+/// An empty `delimiter` causes the stream to yield an error of kind
+/// `InvalidInput`.
+pub fn split_str<A, T>(a: A, delimiter: T) -> SplitStr<A, T>
+    where A: AsyncRead + BufRead,
+          T: AsRef<[u8]>,
+{
+    SplitStr {
+        io: a,
+        delimiter: delimiter,
+        buf: Vec::new(),
+    }
+}
+
+impl<A, T> SplitStr<A, T> {
+    /// Returns the underlying I/O object.
+    ///
+    /// Note that this may lose data already read into internal buffers. It's
+    /// recommended to only call this once the stream has reached its end.
+    pub fn into_inner(self) -> A {
+        self.io
+    }
+}
+
+impl<A, T> Stream for SplitStr<A, T>
+    where A: AsyncRead + BufRead,
+          T: AsRef<[u8]>,
+{
+    type Item = Vec<u8>;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<Vec<u8>>, io::Error> {
+        let delimiter = self.delimiter.as_ref();
+        if delimiter.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      "empty delimiter"));
+        }
+
+        loop {
+            let (found, used) = {
+                let available = try_nb!(self.io.fill_buf());
+                if available.is_empty() {
+                    if self.buf.is_empty() {
+                        return Ok(None.into());
+                    }
+                    return Ok(Some(mem::replace(&mut self.buf, Vec::new())).into());
+                }
+
+                // Everything before `start` has already been searched, but a
+                // delimiter may begin in the tail of the previous read.
+                let prev_len = self.buf.len();
+                let start = prev_len.saturating_sub(delimiter.len() - 1);
+                self.buf.extend_from_slice(available);
+
+                match self.buf[start..].windows(delimiter.len())
+                    .position(|window| window == delimiter)
+                {
+                    Some(i) => {
+                        let end = start + i + delimiter.len();
+                        self.buf.truncate(start + i);
+                        (true, end - prev_len)
+                    }
+                    None => (false, available.len()),
+                }
+            };
+            self.io.consume(used);
+
+            if found {
+                return Ok(Some(mem::replace(&mut self.buf, Vec::new())).into());
+            }
+        }
+    }
+}
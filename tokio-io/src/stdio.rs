@@ -0,0 +1,132 @@
+use std::io::{self, BufRead, Read, Write};
+
+use futures::Poll;
+
+use {AsyncRead, AsyncWrite};
+use blocking::{blocking_read, blocking_write, BlockingRead, BlockingWrite};
+
+/// An asynchronous handle to the standard input of the process.
+///
+/// Created by the [`stdin`] function.
+///
+/// [`stdin`]: fn.stdin.html
+#[derive(Debug)]
+pub struct Stdin {
+    inner: BlockingRead<io::Stdin>,
+}
+
+/// An asynchronous handle to the standard output of the process.
+///
+/// Created by the [`stdout`] function.
+///
+/// [`stdout`]: fn.stdout.html
+#[derive(Debug)]
+pub struct Stdout {
+    inner: BlockingWrite<io::Stdout>,
+}
+
+/// An asynchronous handle to the standard error of the process.
+///
+/// Created by the [`stderr`] function.
+///
+/// [`stderr`]: fn.stderr.html
+#[derive(Debug)]
+pub struct Stderr {
+    inner: BlockingWrite<io::Stderr>,
+}
+
+/// Creates a handle to the standard input of the process which implements
+/// `AsyncRead` and `BufRead`.
+///
+/// Reading from the standard input blocks, so reads are done by a background
+/// thread, up to 8KiB at a time, which is started on the first read and
+/// exits once the handle is dropped. Data is only read from the standard
+/// input when the handle's buffer has been consumed, so no more input is
+/// taken than requested. A read which is in progress when the handle is
+/// dropped can't be cancelled though, and its data is lost.
+///
+/// Like `std::io::Stdin`, reads return 0 bytes at EOF but may return more
+/// data later on, e.g. when reading from a terminal.
+pub fn stdin() -> Stdin {
+    Stdin { inner: blocking_read(io::stdin()) }
+}
+
+/// Creates a handle to the standard output of the process which implements
+/// `AsyncWrite`.
+///
+/// Writing to the standard streams may block, so data written to the handle
+/// is collected in a buffer of 8KiB and handed to a background thread once
+/// the buffer is full or the handle is flushed. The thread is started on the
+/// first write out and is shared by all later writes through this handle;
+/// it exits once the handle is dropped. Data still buffered when the handle
+/// is dropped is written out in the background, without waiting for it.
+///
+/// Every handle writes out its data in order, but data written through
+/// different handles may be interleaved at buffer boundaries.
+pub fn stdout() -> Stdout {
+    Stdout { inner: blocking_write(io::stdout()) }
+}
+
+/// Creates a handle to the standard error of the process which implements
+/// `AsyncWrite`.
+///
+/// This works like [`stdout`], see its documentation for details.
+///
+/// [`stdout`]: fn.stdout.html
+pub fn stderr() -> Stderr {
+    Stderr { inner: blocking_write(io::stderr()) }
+}
+
+impl Read for Stdin {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl BufRead for Stdin {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+impl AsyncRead for Stdin {
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+}
+
+impl Write for Stdout {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl AsyncWrite for Stdout {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.inner.shutdown()
+    }
+}
+
+impl Write for Stderr {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl AsyncWrite for Stderr {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.inner.shutdown()
+    }
+}
@@ -0,0 +1,109 @@
+use std::cmp;
+use std::io::{self, BufRead, Read};
+
+use bytes::Bytes;
+use futures::{Async, Stream};
+
+use AsyncRead;
+
+/// Converts a stream of byte chunks into a reader.
+///
+/// This makes data received as a `Stream` of [`Bytes`], such as from a
+/// channel or an HTTP body, available through `AsyncRead` and `BufRead`, so
+/// it can be parsed with the buffered helpers such as [`read_until`] or
+/// [`lines`]. `fill_buf` returns the rest of the current chunk and only polls
+/// the stream for the next one once that has been consumed, so no data is
+/// copied. Empty chunks are skipped, and the end of the stream is reported as
+/// EOF. Errors from the stream are returned by the read which polled it.
+///
+/// [`Bytes`]: https://docs.rs/bytes/~0.4/bytes/struct.Bytes.html
+/// [`read_until`]: fn.read_until.html
+/// [`lines`]: fn.lines.html
+#[derive(Debug)]
+pub struct StreamReader<S> {
+    stream: S,
+    chunk: Bytes,
+    done: bool,
+}
+
+impl<S> StreamReader<S>
+    where S: Stream<Item = Bytes, Error = io::Error>,
+{
+    /// Creates a new `StreamReader` reading the chunks yielded by `stream`.
+    pub fn new(stream: S) -> StreamReader<S> {
+        StreamReader {
+            stream: stream,
+            chunk: Bytes::new(),
+            done: false,
+        }
+    }
+}
+
+impl<S> StreamReader<S> {
+    /// Returns a reference to the underlying stream.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Returns a mutable reference to the underlying stream.
+    ///
+    /// Note that polling the stream directly will skip over the rest of the
+    /// current chunk.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Returns the part of the current chunk which hasn't been consumed yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.chunk
+    }
+
+    /// Consumes the `StreamReader`, returning the underlying stream and the
+    /// part of the current chunk which hasn't been consumed yet.
+    pub fn into_parts(self) -> (S, Bytes) {
+        (self.stream, self.chunk)
+    }
+}
+
+impl<S> Read for StreamReader<S>
+    where S: Stream<Item = Bytes, Error = io::Error>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = {
+            let mut rem = try!(self.fill_buf());
+            try!(rem.read(buf))
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<S> BufRead for StreamReader<S>
+    where S: Stream<Item = Bytes, Error = io::Error>,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.chunk.is_empty() && !self.done {
+            match try!(self.stream.poll()) {
+                Async::Ready(Some(chunk)) => self.chunk = chunk,
+                Async::Ready(None) => self.done = true,
+                Async::NotReady => {
+                    return Err(io::Error::new(io::ErrorKind::WouldBlock, "would block"))
+                }
+            }
+        }
+        Ok(&self.chunk)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = cmp::min(amt, self.chunk.len());
+        self.chunk.advance(amt);
+    }
+}
+
+impl<S> AsyncRead for StreamReader<S>
+    where S: Stream<Item = Bytes, Error = io::Error>,
+{
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+}
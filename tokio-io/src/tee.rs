@@ -0,0 +1,208 @@
+use std::cmp;
+use std::io::{self, BufRead, Read, Write};
+
+use futures::{Async, Poll};
+
+use {AsyncRead, AsyncWrite};
+
+/// A reader which copies all data read through it to a writer.
+///
+/// This can be used to capture or log the traffic of a connection without
+/// changing the code reading from it.
+///
+/// Data read from the underlying reader is handed to the writer after each
+/// successful read, before the read returns. If the writer isn't ready to
+/// accept all of it, the rest is kept in a buffer and written out on later
+/// reads or by [`poll_flush`], so a slow writer never blocks the reader but
+/// may cause the buffer to grow.
+///
+/// If the reader also implements `BufRead`, every byte is written out once
+/// when it is first returned by `fill_buf`, before it is consumed.
+///
+/// By default errors of the writer are returned from the read which
+/// triggered the write; since the data was already taken out of the reader,
+/// a failed `read` loses it, while a failed `fill_buf` leaves it buffered.
+/// With [`set_ignore_errors`], writer errors are ignored instead and nothing
+/// is written to the writer after the first one.
+///
+/// [`poll_flush`]: #method.poll_flush
+/// [`set_ignore_errors`]: #method.set_ignore_errors
+#[derive(Debug)]
+pub struct TeeRead<R, W> {
+    inner: R,
+    writer: W,
+    // Data which was read but not yet accepted by `writer`
+    pending: Vec<u8>,
+    ignore_errors: bool,
+    failed: bool,
+    // Number of bytes at the start of the inner reader's buffer which were
+    // already passed to `writer`.
+    teed: usize,
+}
+
+impl<R, W> TeeRead<R, W>
+    where R: AsyncRead,
+          W: AsyncWrite,
+{
+    /// Creates a new `TeeRead` which writes all data read from `inner` to
+    /// `writer`.
+    pub fn new(inner: R, writer: W) -> TeeRead<R, W> {
+        TeeRead {
+            inner: inner,
+            writer: writer,
+            pending: Vec::new(),
+            ignore_errors: false,
+            failed: false,
+            teed: 0,
+        }
+    }
+
+    /// Attempts to write all pending data to the writer and flush it.
+    ///
+    /// Returns `Async::Ready` once the writer has accepted and flushed
+    /// everything read so far.
+    pub fn poll_flush(&mut self) -> Poll<(), io::Error> {
+        try!(write_pending(&mut self.writer, &mut self.pending,
+                           self.ignore_errors, &mut self.failed));
+        if !self.pending.is_empty() {
+            return Ok(Async::NotReady);
+        }
+        if self.failed {
+            return Ok(().into());
+        }
+        match self.writer.poll_flush() {
+            Err(_) if self.ignore_errors => {
+                self.failed = true;
+                Ok(().into())
+            }
+            res => res,
+        }
+    }
+}
+
+impl<R, W> TeeRead<R, W> {
+    /// Sets whether errors of the writer are ignored rather than returned
+    /// from reads.
+    pub fn set_ignore_errors(&mut self, ignore_errors: bool) {
+        self.ignore_errors = ignore_errors;
+    }
+
+    /// Returns whether errors of the writer are ignored.
+    pub fn ignores_errors(&self) -> bool {
+        self.ignore_errors
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Note that data read directly from the underlying reader isn't copied
+    /// to the writer.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the writer.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the writer.
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consumes the `TeeRead`, returning the underlying reader and the
+    /// writer.
+    ///
+    /// Data not yet accepted by the writer is lost, so [`poll_flush`] should
+    /// be called first.
+    ///
+    /// [`poll_flush`]: #method.poll_flush
+    pub fn into_inner(self) -> (R, W) {
+        (self.inner, self.writer)
+    }
+}
+
+// Writes as much of `pending` as `writer` accepts without blocking.
+fn write_pending<W: Write>(writer: &mut W,
+                           pending: &mut Vec<u8>,
+                           ignore_errors: bool,
+                           failed: &mut bool)
+                           -> io::Result<()> {
+    if *failed {
+        pending.clear();
+        return Ok(());
+    }
+    let mut written = 0;
+    let mut res = Ok(());
+    while written < pending.len() {
+        match writer.write(&pending[written..]) {
+            Ok(0) => {
+                res = Err(io::Error::new(io::ErrorKind::WriteZero,
+                                         "failed to write whole buffer"));
+                break;
+            }
+            Ok(n) => written += n,
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => break,
+            Err(e) => {
+                res = Err(e);
+                break;
+            }
+        }
+    }
+    pending.drain(..written);
+    if res.is_err() {
+        pending.clear();
+        if ignore_errors {
+            *failed = true;
+            return Ok(());
+        }
+    }
+    res
+}
+
+impl<R: AsyncRead, W: AsyncWrite> Read for TeeRead<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = try!(self.inner.read(buf));
+        // A buffered reader returns its buffered data first, part of which
+        // may have been teed through `fill_buf` already.
+        let seen = cmp::min(n, self.teed);
+        self.teed -= seen;
+        if !self.failed {
+            self.pending.extend_from_slice(&buf[seen..n]);
+        }
+        try!(write_pending(&mut self.writer, &mut self.pending,
+                           self.ignore_errors, &mut self.failed));
+        Ok(n)
+    }
+}
+
+impl<R: AsyncRead + BufRead, W: AsyncWrite> BufRead for TeeRead<R, W> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let buf = try!(self.inner.fill_buf());
+        if buf.len() > self.teed {
+            if !self.failed {
+                self.pending.extend_from_slice(&buf[self.teed..]);
+            }
+            self.teed = buf.len();
+        }
+        try!(write_pending(&mut self.writer, &mut self.pending,
+                           self.ignore_errors, &mut self.failed));
+        Ok(buf)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.teed = self.teed.saturating_sub(amt);
+        self.inner.consume(amt)
+    }
+}
+
+impl<R: AsyncRead, W: AsyncWrite> AsyncRead for TeeRead<R, W> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
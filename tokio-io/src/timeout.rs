@@ -0,0 +1,186 @@
+use std::{fmt, io};
+use std::io::{BufRead, Read, Write};
+use std::time::Duration;
+
+use futures::{Async, Future, Poll};
+
+use {AsyncRead, AsyncWrite};
+
+/// A source of delays used by [`Timeout`].
+///
+/// `tokio-io` doesn't contain a timer of its own, so this trait allows
+/// plugging in whichever timer the application uses.
+///
+/// [`Timeout`]: struct.Timeout.html
+pub trait Clock {
+    /// The future returned by `delay`.
+    type Delay: Future<Item = (), Error = io::Error>;
+
+    /// Returns a future which completes once `duration` has elapsed.
+    ///
+    /// The future must notify the current task when it completes, like any
+    /// other future.
+    fn delay(&mut self, duration: Duration) -> Self::Delay;
+}
+
+/// Wraps an `AsyncRead` and fails reads which don't complete in time.
+///
+/// Whenever a read on the underlying reader would block, a delay of the
+/// configured duration is started using the `Clock`. If the reader is still
+/// not readable once the delay has elapsed, the read fails with an error of
+/// kind `TimedOut`. The delay is cancelled as soon as a read completes, so
+/// the duration limits how long a single read may wait for data, not the
+/// total time spent reading.
+///
+/// If the underlying reader implements `BufRead`, so does `Timeout`, with
+/// the same limit applied to refilling the buffer. Writes are passed through
+/// without a timeout.
+pub struct Timeout<R, C: Clock> {
+    inner: R,
+    clock: C,
+    duration: Duration,
+    delay: Option<C::Delay>,
+}
+
+impl<R, C> Timeout<R, C>
+    where R: AsyncRead,
+          C: Clock + Default,
+{
+    /// Creates a new `Timeout` which fails reads on `inner` that block for
+    /// longer than `duration`, using the default instance of the `Clock`.
+    pub fn new(inner: R, duration: Duration) -> Timeout<R, C> {
+        Timeout::with_clock(inner, duration, C::default())
+    }
+}
+
+impl<R, C> Timeout<R, C>
+    where R: AsyncRead,
+          C: Clock,
+{
+    /// Creates a new `Timeout` which fails reads on `inner` that block for
+    /// longer than `duration`, using `clock` to create the delays.
+    pub fn with_clock(inner: R, duration: Duration, clock: C) -> Timeout<R, C> {
+        Timeout {
+            inner: inner,
+            clock: clock,
+            duration: duration,
+            delay: None,
+        }
+    }
+}
+
+impl<R, C: Clock> Timeout<R, C> {
+    /// Returns the duration after which a blocked read fails.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the `Timeout`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+// Called when the underlying reader would block, starts the delay if needed
+// and returns the error to report for the read. Takes the fields separately so
+// that `fill_buf` can call it while the inner reader's buffer is borrowed.
+fn poll_delay<C: Clock>(clock: &mut C,
+                        delay: &mut Option<C::Delay>,
+                        duration: Duration)
+                        -> io::Error {
+    if delay.is_none() {
+        *delay = Some(clock.delay(duration));
+    }
+    let res = delay.as_mut().unwrap().poll();
+    match res {
+        Ok(Async::NotReady) => {
+            io::Error::new(io::ErrorKind::WouldBlock, "would block")
+        }
+        Ok(Async::Ready(())) => {
+            *delay = None;
+            io::Error::new(io::ErrorKind::TimedOut, "read timed out")
+        }
+        Err(e) => {
+            *delay = None;
+            e
+        }
+    }
+}
+
+impl<R: AsyncRead, C: Clock> Read for Timeout<R, C> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.inner.read(buf) {
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                Err(poll_delay(&mut self.clock, &mut self.delay, self.duration))
+            }
+            res => {
+                self.delay = None;
+                res
+            }
+        }
+    }
+}
+
+impl<R: AsyncRead + BufRead, C: Clock> BufRead for Timeout<R, C> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match self.inner.fill_buf() {
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                Err(poll_delay(&mut self.clock, &mut self.delay, self.duration))
+            }
+            res => {
+                self.delay = None;
+                res
+            }
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+impl<R: AsyncRead, C: Clock> AsyncRead for Timeout<R, C> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<R: Write, C: Clock> Write for Timeout<R, C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<R: AsyncWrite, C: Clock> AsyncWrite for Timeout<R, C> {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.inner.shutdown()
+    }
+}
+
+impl<R, C> fmt::Debug for Timeout<R, C>
+    where R: fmt::Debug,
+          C: Clock + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Timeout")
+            .field("inner", &self.inner)
+            .field("clock", &self.clock)
+            .field("duration", &self.duration)
+            .field("is_delayed", &self.delay.is_some())
+            .finish()
+    }
+}
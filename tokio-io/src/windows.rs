@@ -0,0 +1,79 @@
+use std::cmp;
+use std::io::{self, BufRead};
+
+use futures::{Async, Poll, Stream};
+
+use AsyncRead;
+
+/// Combinator created by the top-level `windows` method which is a stream
+/// over the overlapping windows of an I/O object.
+#[derive(Debug)]
+pub struct Windows<A> {
+    io: A,
+    size: usize,
+    window: Vec<u8>,
+    advance: bool,
+}
+
+/// Creates a new stream from the I/O object given yielding every window of
+/// `size` consecutive bytes of its input.
+///
+/// Like `slice::windows` the windows overlap: each one starts one byte after
+/// the previous one. This is useful for searching for multi-byte patterns,
+/// such as a magic number, which may be split across reads. Since a stream
+/// can't lend out its internal buffer, every window is yielded as a copy.
+/// The stream ends once fewer than `size` bytes remain, so an input shorter
+/// than `size` yields no windows at all.
+///
+/// # Panics
+///
+/// Panics if `size` is 0.
+pub fn windows<A>(a: A, size: usize) -> Windows<A>
+    where A: AsyncRead + BufRead,
+{
+    assert!(size != 0, "window size must be non-zero");
+    Windows {
+        io: a,
+        size: size,
+        window: Vec::with_capacity(size),
+        advance: false,
+    }
+}
+
+impl<A> Windows<A> {
+    /// Returns the underlying I/O object.
+    ///
+    /// Note that the bytes of the last window yielded have already been
+    /// consumed from it.
+    pub fn into_inner(self) -> A {
+        self.io
+    }
+}
+
+impl<A> Stream for Windows<A>
+    where A: AsyncRead + BufRead,
+{
+    type Item = Vec<u8>;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<Vec<u8>>, io::Error> {
+        if self.advance {
+            self.window.remove(0);
+            self.advance = false;
+        }
+        while self.window.len() < self.size {
+            let n = {
+                let available = try_nb!(self.io.fill_buf());
+                if available.is_empty() {
+                    return Ok(Async::Ready(None));
+                }
+                let n = cmp::min(available.len(), self.size - self.window.len());
+                self.window.extend_from_slice(&available[..n]);
+                n
+            };
+            self.io.consume(n);
+        }
+        self.advance = true;
+        Ok(Async::Ready(Some(self.window.clone())))
+    }
+}
@@ -0,0 +1,63 @@
+use std::io;
+
+use futures::Poll;
+
+use AsyncWrite;
+
+/// A writer which collects all data written to it in memory.
+///
+/// Created by the [`writer`] function.
+///
+/// [`writer`]: fn.writer.html
+#[derive(Debug, Default, Clone)]
+pub struct Writer {
+    buf: Vec<u8>,
+}
+
+/// Creates a writer which collects all data written to it into a `Vec<u8>`.
+///
+/// Writes, flushes and shutdowns always complete immediately, and the data
+/// written can be retrieved with [`Writer::into_inner`]. This is mostly
+/// useful for capturing the output of an `AsyncWrite` consumer in tests,
+/// without having to wrap a vector in a `Cursor`.
+///
+/// [`Writer::into_inner`]: struct.Writer.html#method.into_inner
+pub fn writer() -> Writer {
+    Writer {
+        buf: Vec::new(),
+    }
+}
+
+impl Writer {
+    /// Returns the data which has been written so far.
+    pub fn get_ref(&self) -> &Vec<u8> {
+        &self.buf
+    }
+
+    /// Returns a mutable reference to the data which has been written so far.
+    pub fn get_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.buf
+    }
+
+    /// Consumes the `Writer`, returning all data written to it.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+impl io::Write for Writer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for Writer {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        Ok(().into())
+    }
+}
@@ -0,0 +1,52 @@
+use std::io;
+
+use AsyncRead;
+
+static ZEROS: [u8; 256] = [0; 256];
+
+/// A reader which yields an endless stream of zero bytes.
+///
+/// Unlike [`repeat`] it doesn't need a buffer of its own, so it's `Copy`.
+///
+/// Created by the [`zero`] function.
+///
+/// [`repeat`]: fn.repeat.html
+/// [`zero`]: fn.zero.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Zero {
+    _priv: (),
+}
+
+/// Creates a reader which infinitely yields `\0` bytes, like `/dev/zero`.
+///
+/// All reads from this reader succeed immediately by filling the provided
+/// buffer with zeroes, and the buffered contents are always a non-empty run
+/// of zeroes. Combined with `Read::take` this provides a zero-filled input of
+/// a known length for tests.
+pub fn zero() -> Zero {
+    Zero { _priv: () }
+}
+
+impl io::Read for Zero {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        for slot in &mut *buf {
+            *slot = 0;
+        }
+        Ok(buf.len())
+    }
+}
+
+impl io::BufRead for Zero {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(&ZEROS)
+    }
+
+    fn consume(&mut self, _amt: usize) {
+    }
+}
+
+impl AsyncRead for Zero {
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+}
@@ -3,10 +3,14 @@ extern crate bytes;
 extern crate futures;
 
 use tokio_io::AsyncRead;
+use tokio_io::io::{blocking_read, read_into_buf, read_to_buf, read_to_end, split_at_limit,
+                   ReadBuf};
 use bytes::{BytesMut, BufMut};
-use futures::Async;
+use futures::{future, Async, Future};
 
-use std::io::{self, Read};
+use std::cell::Cell;
+use std::io::{self, BufRead, Cursor, Read};
+use std::sync::mpsc;
 
 #[test]
 fn read_buf_success() {
@@ -147,3 +151,235 @@ fn read_buf_translate_wouldblock_to_not_ready() {
     let mut buf = BytesMut::with_capacity(65);
     assert!(!R.read_buf(&mut buf).unwrap().is_ready());
 }
+
+#[test]
+fn read_vectored_reads_first_non_empty() {
+    let mut rd = &b"hello world"[..];
+    let mut a = [0; 0];
+    let mut b = [0; 3];
+    let mut c = [0; 16];
+
+    {
+        let mut bufs: [&mut [u8]; 3] = [&mut a, &mut b, &mut c];
+        assert_eq!(Async::Ready(3), rd.poll_read_vectored(&mut bufs).unwrap());
+    }
+    assert_eq!(&b, b"hel");
+    assert_eq!(&c, &[0; 16]);
+
+    let mut bufs: [&mut [u8]; 1] = [&mut a];
+    assert_eq!(Async::Ready(0), rd.poll_read_vectored(&mut bufs).unwrap());
+    assert_eq!(rd, b"lo world");
+}
+
+#[test]
+fn read_vectored_stops_on_not_ready() {
+    struct R(Vec<io::Result<usize>>);
+
+    impl Read for R {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = try!(self.0.remove(0));
+            for b in buf[..n].iter_mut() {
+                *b = b'a';
+            }
+            Ok(n)
+        }
+    }
+
+    impl AsyncRead for R {}
+
+    let mut rd = R(vec![Ok(2),
+                        Err(io::Error::new(io::ErrorKind::WouldBlock, "")),
+                        Err(io::Error::new(io::ErrorKind::Other, ""))]);
+    let mut a = [0; 2];
+    let mut b = [0; 2];
+
+    {
+        let mut bufs: [&mut [u8]; 2] = [&mut a, &mut b];
+        assert_eq!(Async::Ready(2), rd.poll_read_vectored(&mut bufs).unwrap());
+        assert!(!rd.poll_read_vectored(&mut bufs).unwrap().is_ready());
+        let err = rd.poll_read_vectored(&mut bufs).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+    }
+    assert_eq!(&a, b"aa");
+    assert_eq!(&b, &[0, 0]);
+}
+
+#[test]
+fn read_buf_prepares_once() {
+    struct R {
+        prepared: Cell<usize>,
+    }
+
+    impl Read for R {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            buf[..3].copy_from_slice(b"abc");
+            Ok(3)
+        }
+    }
+
+    impl AsyncRead for R {
+        unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+            self.prepared.set(self.prepared.get() + 1);
+            for b in buf.iter_mut() {
+                *b = 0;
+            }
+            true
+        }
+    }
+
+    let mut rd = R { prepared: Cell::new(0) };
+    let mut storage = [0xff; 8];
+    let mut buf = ReadBuf::uninit(&mut storage);
+    assert_eq!(buf.initialized_len(), 0);
+
+    assert_eq!(rd.poll_read_buf(&mut buf).unwrap(), Async::Ready(3));
+    assert_eq!(buf.filled(), b"abc");
+    assert_eq!(buf.initialized_len(), 8);
+
+    buf.clear();
+    assert_eq!(rd.poll_read_buf(&mut buf).unwrap(), Async::Ready(3));
+    assert_eq!(rd.poll_read_buf(&mut buf).unwrap(), Async::Ready(3));
+    assert_eq!(buf.filled(), b"abcabc");
+    assert_eq!(buf.remaining(), 2);
+    assert_eq!(rd.prepared.get(), 1);
+}
+
+#[test]
+fn read_buf_put_slice() {
+    let mut storage = [0; 4];
+    let mut buf = ReadBuf::new(&mut storage);
+    buf.put_slice(b"ab");
+    buf.add_filled(1);
+    assert_eq!(buf.filled(), b"ab\0");
+    assert_eq!(buf.remaining(), 1);
+}
+
+#[test]
+fn read_into_buf_future() {
+    let rd = Cursor::new(b"hello world".to_vec());
+    let mut buf = BytesMut::with_capacity(64);
+    buf.put_slice(b">");
+
+    let (rd, mut buf, n) = read_into_buf(rd, buf).wait().unwrap();
+    assert_eq!(n, 11);
+    assert_eq!(buf, ">hello world");
+
+    buf.reserve(64);
+    let (_, buf, n) = read_into_buf(rd, buf).wait().unwrap();
+    assert_eq!(n, 0);
+    assert_eq!(buf.len(), 12);
+}
+
+#[test]
+fn read_to_buf_grows() {
+    let data: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+    let rd = Cursor::new(data.clone());
+    let mut buf = BytesMut::new();
+    buf.put_slice(b"x");
+
+    let (_, buf) = read_to_buf(rd, buf).wait().unwrap();
+    assert_eq!(buf.len(), 1001);
+    assert_eq!(&buf[..1], b"x");
+    assert_eq!(&buf[1..], &data[..]);
+}
+
+#[test]
+fn split_at_limit_sections() {
+    let (take, mut rest) = split_at_limit(Cursor::new(b"headerbody".to_vec()), 6);
+    assert_eq!(take.limit(), 6);
+
+    let res = future::lazy(|| rest.read(&mut [0; 4])).wait();
+    assert_eq!(res.unwrap_err().kind(), io::ErrorKind::WouldBlock);
+
+    let (take, head) = read_to_end(take, Vec::new()).wait().unwrap();
+    assert_eq!(head, b"header");
+    assert_eq!(take.limit(), 0);
+
+    let (_, body) = read_to_end(rest, Vec::new()).wait().unwrap();
+    assert_eq!(body, b"body");
+}
+
+#[test]
+fn split_at_limit_skips_unread() {
+    let (mut take, rest) = split_at_limit(Cursor::new(b"headerbody".to_vec()), 6);
+    let mut buf = [0; 2];
+    assert_eq!(take.read(&mut buf).unwrap(), 2);
+    drop(take);
+
+    let (_, body) = read_to_end(rest, Vec::new()).wait().unwrap();
+    assert_eq!(body, b"body");
+}
+
+#[test]
+fn split_at_limit_ends_at_eof() {
+    let (take, mut rest) = split_at_limit(Cursor::new(b"short".to_vec()), 10);
+    let mut rest = future::lazy(move || {
+        let mut buf = [0; 2];
+        assert_eq!(rest.poll_read(&mut buf).unwrap(), Async::NotReady);
+        Ok::<_, io::Error>(rest)
+    }).wait().unwrap();
+
+    let (take, buf) = read_to_end(take, Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"short");
+    assert_eq!(take.limit(), 0);
+
+    let mut buf = [0; 2];
+    assert_eq!(rest.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn blocking_read_reads_in_background() {
+    let rd = blocking_read(Cursor::new(b"hello world".to_vec()));
+    let (mut rd, buf) = read_to_end(rd, Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"hello world");
+
+    let mut buf = [0; 4];
+    assert_eq!(rd.read(&mut buf).unwrap(), 0);
+    assert!(rd.fill_buf().unwrap().is_empty());
+}
+
+#[test]
+fn blocking_read_would_block() {
+    struct R(mpsc::Receiver<Vec<u8>>);
+
+    impl Read for R {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.recv() {
+                Ok(data) => {
+                    buf[..data.len()].copy_from_slice(&data);
+                    Ok(data.len())
+                }
+                Err(_) => Ok(0),
+            }
+        }
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let mut rd = blocking_read(R(rx));
+    let rd = future::lazy(move || {
+        let mut buf = [0; 4];
+        assert_eq!(rd.poll_read(&mut buf).unwrap(), Async::NotReady);
+        assert_eq!(rd.fill_buf().unwrap_err().kind(), io::ErrorKind::WouldBlock);
+        Ok::<_, io::Error>(rd)
+    }).wait().unwrap();
+
+    tx.send(b"abc".to_vec()).unwrap();
+    tx.send(b"def".to_vec()).unwrap();
+    drop(tx);
+    let (_, buf) = read_to_end(rd, Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"abcdef");
+}
+
+#[test]
+fn blocking_read_passes_on_errors() {
+    struct R;
+
+    impl Read for R {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "boom"))
+        }
+    }
+
+    let err = read_to_end(blocking_read(R), Vec::new()).wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+}
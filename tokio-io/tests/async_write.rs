@@ -0,0 +1,146 @@
+extern crate tokio_io;
+extern crate futures;
+
+use tokio_io::AsyncWrite;
+use tokio_io::io::{blocking_write, copy, count_write, empty, flush, read_to_end, repeat, shutdown,
+                   sink, stderr, stdout, write_all, writer, LimitedWrite};
+use futures::{Async, Future};
+
+use std::io::{self, Cursor, Read, Write};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn sink_discards_writes() {
+    let (wr, buf) = write_all(sink(), vec![1; 64 * 1024]).wait().unwrap();
+    assert_eq!(buf.len(), 64 * 1024);
+
+    let wr = flush(wr).wait().unwrap();
+    let mut wr = shutdown(wr).wait().unwrap();
+    assert_eq!(Async::Ready(5), wr.poll_write(b"hello").unwrap());
+}
+
+#[test]
+fn sink_copy() {
+    let (n, _, _) = copy(repeat(b'a').take(10_000), sink()).wait().unwrap();
+    assert_eq!(n, 10_000);
+    assert!(format!("{:?}", sink()).starts_with("Sink"));
+}
+
+#[test]
+fn empty_reads_and_writes() {
+    let (rd, buf) = read_to_end(empty(), Vec::new()).wait().unwrap();
+    assert!(buf.is_empty());
+
+    let (wr, _) = write_all(rd, vec![1; 1024]).wait().unwrap();
+    let wr = flush(wr).wait().unwrap();
+    let mut wr = shutdown(wr).wait().unwrap();
+    assert_eq!(Async::Ready(5), wr.poll_write(b"hello").unwrap());
+}
+
+#[test]
+fn writer_collects_writes() {
+    let (wr, _) = write_all(writer(), b"hello ").wait().unwrap();
+    let (wr, _) = write_all(wr, b"world").wait().unwrap();
+    let wr = shutdown(wr).wait().unwrap();
+    assert_eq!(wr.get_ref(), b"hello world");
+    assert_eq!(wr.into_inner(), b"hello world".to_vec());
+}
+
+#[test]
+fn limited_write_fails_after_limit() {
+    let wr = LimitedWrite::new(Cursor::new(Vec::new()), 5);
+    let (mut wr, _) = write_all(wr, b"abc").wait().unwrap();
+
+    assert_eq!(wr.write(b"defg").unwrap(), 2);
+    assert_eq!(wr.bytes_written(), 5);
+    assert_eq!(wr.write(b"").unwrap(), 0);
+    assert_eq!(wr.write(b"h").unwrap_err().kind(), io::ErrorKind::WriteZero);
+
+    wr.set_error_kind(io::ErrorKind::BrokenPipe);
+    let err = write_all(wr, b"h").wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn count_write_counts_accepted_bytes() {
+    let wr = count_write(LimitedWrite::new(Cursor::new(Vec::new()), 5));
+    let (mut wr, _) = write_all(wr, b"abc").wait().unwrap();
+    assert_eq!(wr.bytes_written(), 3);
+
+    assert_eq!(wr.write(b"defg").unwrap(), 2);
+    assert_eq!(wr.bytes_written(), 5);
+    assert!(wr.write(b"h").is_err());
+    assert_eq!(wr.bytes_written(), 5);
+    assert_eq!(wr.into_inner().into_inner().into_inner(), b"abcde");
+}
+
+#[test]
+fn blocking_write_writes_in_background() {
+    #[derive(Clone)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let written = Shared(Arc::new(Mutex::new(Vec::new())));
+    let data = (0..20_000).map(|i| i as u8).collect::<Vec<_>>();
+    let (wr, _) = write_all(blocking_write(written.clone()), data.clone()).wait().unwrap();
+    let wr = flush(wr).wait().unwrap();
+    assert_eq!(*written.0.lock().unwrap(), data);
+
+    let (wr, _) = write_all(wr, b"tail").wait().unwrap();
+    assert_eq!(written.0.lock().unwrap().len(), data.len());
+    shutdown(wr).wait().unwrap();
+    assert_eq!(&written.0.lock().unwrap()[data.len()..], b"tail");
+}
+
+#[test]
+fn stdout_flushes_without_output() {
+    let wr = flush(stdout()).wait().unwrap();
+    shutdown(wr).wait().unwrap();
+    flush(stderr()).wait().unwrap();
+}
+
+#[test]
+fn mut_ref_forwards_poll_methods() {
+    struct W {
+        flushes: usize,
+    }
+
+    impl Write for W {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            panic!("flush called instead of poll_flush")
+        }
+    }
+
+    impl AsyncWrite for W {
+        fn poll_flush(&mut self) -> futures::Poll<(), io::Error> {
+            self.flushes += 1;
+            Ok(Async::Ready(()))
+        }
+
+        fn shutdown(&mut self) -> futures::Poll<(), io::Error> {
+            Ok(().into())
+        }
+    }
+
+    let mut wr = W { flushes: 0 };
+    let (_, buf) = write_all(&mut wr, b"hello").wait().unwrap();
+    assert_eq!(buf, b"hello");
+    flush(&mut wr).wait().unwrap();
+    assert_eq!(wr.flushes, 1);
+
+    let mut boxed: Box<AsyncWrite> = Box::new(W { flushes: 0 });
+    assert_eq!(boxed.poll_flush().unwrap(), Async::Ready(()));
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "bincode-codec")]
+
+extern crate tokio_io;
+extern crate bytes;
+
+use bytes::{BufMut, BytesMut};
+use tokio_io::codec::{BincodeCodec, BincodeCodecError, Decoder, Encoder};
+
+use std::io;
+
+#[test]
+fn bincode_round_trip() {
+    let mut codec = BincodeCodec::<(u16, String)>::new();
+    let mut buf = BytesMut::new();
+    codec.encode((7, "hi".to_string()), &mut buf).unwrap();
+    assert_eq!(&buf[..], &b"\x00\x00\x00\x0c\x07\x00\x02\x00\x00\x00\x00\x00\x00\x00hi"[..]);
+    codec.encode((8, "yo".to_string()), &mut buf).unwrap();
+
+    let mut partial = buf.split_to(10);
+    assert!(codec.decode(&mut partial).unwrap().is_none());
+    partial.unsplit(buf);
+    let mut buf = partial;
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some((7, "hi".to_string())));
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some((8, "yo".to_string())));
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+}
+
+#[test]
+fn bincode_config() {
+    let mut codec = BincodeCodec::<Vec<u32>>::config()
+        .length_field_length(1)
+        .big_endian()
+        .varint_encoding()
+        .new_codec();
+    let mut buf = BytesMut::new();
+    codec.encode(vec![1, 1000], &mut buf).unwrap();
+    assert_eq!(&buf[..], &b"\x05\x02\x01\xfb\x03\xe8"[..]);
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some(vec![1, 1000]));
+}
+
+#[test]
+fn bincode_errors() {
+    let mut codec = BincodeCodec::<u32>::config()
+        .max_frame_length(4)
+        .new_codec();
+    let mut buf = BytesMut::new();
+    buf.reserve(20);
+    buf.put_slice(b"\x00\x00\x00\x02ab");
+    match codec.decode(&mut buf) {
+        Err(BincodeCodecError::Bincode(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    buf.put_slice(b"\x00\x00\x00\x05");
+    match codec.decode(&mut buf) {
+        Err(BincodeCodecError::Io(ref e)) if e.kind() == io::ErrorKind::InvalidData => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let mut codec = BincodeCodec::<u64>::config()
+        .max_frame_length(4)
+        .new_codec();
+    match codec.encode(1, &mut buf) {
+        Err(BincodeCodecError::Io(ref e)) if e.kind() == io::ErrorKind::InvalidInput => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
@@ -0,0 +1,501 @@
+extern crate tokio_io;
+extern crate futures;
+
+use tokio_io::io::{copy_buf, copy_exact, count_bytes, inspect, lines, lines_bytes,
+                   parse_lines, read_exact, read_exact_buf, read_n_bytes, read_to_end,
+                   read_to_end_buf, read_to_string_buf, read_until, read_until_any, read_while,
+                   repeat, scan, skip_bytes, skip_until, split_once, split_str, windows,
+                   zero, AllowStdIo, LimitedRead, TeeRead};
+use tokio_io::AsyncRead;
+use futures::{Future, Stream};
+
+use std::io::{self, BufRead, Cursor, Read, Write};
+
+#[test]
+fn read_until_any_matches_first_delimiter() {
+    let rd = Cursor::new(b"abc\r\ndef\nghi".to_vec());
+
+    let (rd, buf, matched) = read_until_any(rd, b"\r\n", Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"abc\r");
+    assert_eq!(matched, Some(b'\r'));
+
+    let (rd, buf, matched) = read_until_any(rd, b"\r\n", Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"\n");
+    assert_eq!(matched, Some(b'\n'));
+
+    let (rd, buf, matched) = read_until_any(rd, b"\n", Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"def\n");
+    assert_eq!(matched, Some(b'\n'));
+
+    let (_, buf, matched) = read_until_any(rd, b"\n", Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"ghi");
+    assert_eq!(matched, None);
+}
+
+#[test]
+fn read_until_any_empty_delimiters() {
+    let rd = Cursor::new(b"abc".to_vec());
+    let err = read_until_any(rd, b"", Vec::new()).wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn take_limits_buffered_reads() {
+    let rd = Cursor::new(b"hello world".to_vec()).take(5);
+
+    let (rd, buf) = read_until(rd, b' ', Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"hello");
+    assert_eq!(rd.limit(), 0);
+
+    let mut rd = rd.into_inner();
+    let (_, buf) = read_to_end(&mut rd, Vec::new()).wait().unwrap();
+    assert_eq!(buf, b" world");
+}
+
+#[test]
+fn chain_continues_into_second_reader() {
+    let rd = Cursor::new(b"hel".to_vec()).chain(Cursor::new(b"lo\nworld".to_vec()));
+
+    let (rd, buf) = read_until(rd, b'\n', Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"hello\n");
+
+    let (_, buf) = read_until(rd, b'\n', Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"world");
+}
+
+#[test]
+fn cursor_readers() {
+    fn first_line<T: AsRef<[u8]>>(rd: Cursor<T>) -> Vec<u8> {
+        let (rd, buf) = read_until(rd, b'\n', Vec::new()).wait().unwrap();
+        assert_eq!(rd.position(), buf.len() as u64);
+        buf
+    }
+
+    let data = b"one\ntwo";
+    assert_eq!(first_line(Cursor::new(data.to_vec())), b"one\n");
+    assert_eq!(first_line(Cursor::new(&data[..])), b"one\n");
+    assert_eq!(first_line(Cursor::new(data.to_vec().into_boxed_slice())), b"one\n");
+}
+
+#[test]
+fn repeat_never_ends() {
+    let (rd, buf) = read_exact(repeat(b'a'), [0; 1024]).wait().unwrap();
+    assert!(buf.iter().all(|&b| b == b'a'));
+
+    let (_, buf, matched) = read_until_any(rd.take(1000), b"b", Vec::new()).wait().unwrap();
+    assert_eq!(buf, vec![b'a'; 1000]);
+    assert_eq!(matched, None);
+}
+
+#[test]
+fn zero_with_take() {
+    let (_, buf) = read_to_end(zero().take(1000), vec![1]).wait().unwrap();
+    assert_eq!(buf.len(), 1001);
+    assert!(buf[1..].iter().all(|b| *b == 0));
+
+    let (_, buf) = read_until(zero().take(3), b'\n', Vec::new()).wait().unwrap();
+    assert_eq!(buf, [0; 3]);
+}
+
+#[test]
+fn repeat_clone() {
+    let rd = repeat(b'z');
+    let (_, buf) = read_exact(rd.clone(), [0; 4]).wait().unwrap();
+    assert_eq!(&buf, b"zzzz");
+    let (_, buf) = read_exact(rd, [0; 2]).wait().unwrap();
+    assert_eq!(&buf, b"zz");
+}
+
+#[test]
+fn copy_buf_drains_reader() {
+    let rd = io::BufReader::with_capacity(4, Cursor::new(b"hello world".to_vec()));
+
+    let (n, _, wr) = copy_buf(rd, Cursor::new(Vec::new())).wait().unwrap();
+    assert_eq!(n, 11);
+    assert_eq!(wr.get_ref(), b"hello world");
+}
+
+#[test]
+fn copy_exact_stops_at_n() {
+    let rd = io::BufReader::with_capacity(4, Cursor::new(b"hello world".to_vec()));
+
+    let (mut rd, wr) = copy_exact(rd, Cursor::new(Vec::new()), 7).wait().unwrap();
+    assert_eq!(wr.get_ref(), b"hello w");
+    let mut rest = String::new();
+    rd.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "orld");
+
+    let rd = Cursor::new(b"short".to_vec());
+    let err = copy_exact(rd, Cursor::new(Vec::new()), 6).wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn allow_std_buf_read() {
+    let rd = io::BufReader::new(&b"one\ntwo\n"[..]);
+    let rd = AllowStdIo::new(rd);
+
+    let lines = lines(rd).collect().wait().unwrap();
+    assert_eq!(lines, vec!["one".to_string(), "two".to_string()]);
+}
+
+#[test]
+fn split_str_across_reads() {
+    let rd = io::BufReader::with_capacity(3, Cursor::new(b"a--b---c--".to_vec()));
+
+    let segments = split_str(rd, "--").collect().wait().unwrap();
+    assert_eq!(segments, vec![b"a".to_vec(), b"b".to_vec(), b"-c".to_vec()]);
+}
+
+#[test]
+fn split_str_trailing_segment() {
+    let rd = Cursor::new(b"one\r\ntwo".to_vec());
+
+    let segments = split_str(rd, "\r\n").collect().wait().unwrap();
+    assert_eq!(segments, vec![b"one".to_vec(), b"two".to_vec()]);
+}
+
+#[test]
+fn read_exact_buf_uses_buffer() {
+    let rd = io::BufReader::with_capacity(4, Cursor::new(b"hello world".to_vec()));
+
+    let (rd, buf) = read_exact_buf(rd, [0; 6]).wait().unwrap();
+    assert_eq!(&buf, b"hello ");
+
+    let err = read_exact_buf(rd, [0; 6]).wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn buffered_helpers_are_debug() {
+    fn rd() -> Cursor<Vec<u8>> {
+        Cursor::new(b"a\nb".to_vec())
+    }
+
+    assert!(format!("{:?}", read_until(rd(), b'\n', Vec::new())).starts_with("ReadUntil"));
+    assert!(format!("{:?}", read_until_any(rd(), b"\n", Vec::new())).starts_with("ReadUntilAny"));
+    assert!(format!("{:?}", read_exact_buf(rd(), [0; 1])).starts_with("ReadExactBuf"));
+    assert!(format!("{:?}", copy_buf(rd(), Cursor::new(Vec::new()))).starts_with("CopyBuf"));
+    assert!(format!("{:?}", lines(rd())).starts_with("Lines"));
+    assert!(format!("{:?}", split_str(rd(), "\n")).starts_with("SplitStr"));
+    assert!(format!("{:?}", repeat(0)).starts_with("Repeat"));
+}
+
+#[test]
+fn read_to_end_buf_keeps_existing_data() {
+    let rd = io::BufReader::with_capacity(4, Cursor::new(b"hello world".to_vec()));
+
+    let (_, buf) = read_to_end_buf(rd, b">> ".to_vec()).wait().unwrap();
+    assert_eq!(buf, b">> hello world");
+}
+
+#[test]
+fn read_to_string_buf_split_character() {
+    // The two byte encoding of 'é' is cut in half by the buffer capacity
+    let rd = io::BufReader::with_capacity(2, Cursor::new("aé b".as_bytes().to_vec()));
+
+    let (_, buf) = read_to_string_buf(rd, String::new()).wait().unwrap();
+    assert_eq!(buf, "aé b");
+}
+
+#[test]
+fn read_to_string_buf_invalid() {
+    let rd = io::BufReader::with_capacity(2, Cursor::new(b"ab\xc3\xa9\xffcd".to_vec()));
+    let mut fut = read_to_string_buf(rd, String::from(">"));
+    let err = fut.poll().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    let (_, buf) = fut.into_inner();
+    assert_eq!(buf, ">abé");
+
+    let rd = Cursor::new(b"ab\xc3".to_vec());
+    let mut fut = read_to_string_buf(rd, String::new());
+    let err = fut.poll().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    let (_, buf) = fut.into_inner();
+    assert_eq!(buf, "ab");
+}
+
+#[test]
+fn read_to_string_buf_keeps_text_on_io_error() {
+    #[derive(Debug)]
+    struct R(Vec<u8>);
+
+    impl Read for R {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            unreachable!()
+        }
+    }
+
+    impl BufRead for R {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            if self.0.is_empty() {
+                return Err(io::Error::new(io::ErrorKind::Other, "boom"));
+            }
+            Ok(&self.0)
+        }
+
+        fn consume(&mut self, amt: usize) {
+            self.0.drain(..amt);
+        }
+    }
+
+    impl AsyncRead for R {}
+
+    let mut fut = read_to_string_buf(R(b"ab\xc3".to_vec()), String::new());
+    let err = fut.poll().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+    let (_, buf) = fut.into_inner();
+    assert_eq!(buf, "ab");
+}
+
+#[test]
+fn skip_until_discards_through_delimiter() {
+    let rd = io::BufReader::with_capacity(2, Cursor::new(b"header;body".to_vec()));
+
+    let (rd, skipped) = skip_until(rd, b';').wait().unwrap();
+    assert_eq!(skipped, 7);
+
+    let (rd, buf) = read_to_end(rd, Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"body");
+
+    let (_, skipped) = skip_until(rd, b';').wait().unwrap();
+    assert_eq!(skipped, 0);
+}
+
+#[test]
+fn buffered_helpers_by_ref() {
+    let mut rd = io::BufReader::with_capacity(2, Cursor::new(b"abc\ndef".to_vec()));
+
+    let (_, buf) = read_until(rd.by_ref(), b'\n', Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"abc\n");
+
+    let (_, skipped) = skip_until(&mut rd, b'e').wait().unwrap();
+    assert_eq!(skipped, 2);
+
+    let (_, buf) = read_to_end_buf(rd, Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"f");
+}
+
+#[test]
+fn boxed_buf_readers() {
+    trait AsyncBufRead: AsyncRead + BufRead {}
+    impl<T: AsyncRead + BufRead> AsyncBufRead for T {}
+
+    let readers: Vec<Box<AsyncBufRead>> = vec![
+        Box::new(Cursor::new(b"abc\n".to_vec())),
+        Box::new(io::BufReader::new(&b"def\n"[..])),
+    ];
+
+    let lines = readers.into_iter()
+        .map(|rd| read_until(rd, b'\n', Vec::new()).wait().unwrap().1)
+        .collect::<Vec<_>>();
+    assert_eq!(lines, vec![b"abc\n".to_vec(), b"def\n".to_vec()]);
+}
+
+#[test]
+fn count_bytes_counts_consumed_bytes() {
+    let rd = io::BufReader::with_capacity(2, Cursor::new(b"abc\ndef".to_vec()));
+    let rd = count_bytes(rd);
+
+    let (mut rd, _) = read_until(rd, b'\n', Vec::new()).wait().unwrap();
+    assert_eq!(rd.bytes_read(), 4);
+
+    assert_eq!(rd.fill_buf().unwrap(), b"de");
+    assert_eq!(rd.bytes_read(), 4);
+
+    let (rd, _) = read_to_end(rd, Vec::new()).wait().unwrap();
+    assert_eq!(rd.bytes_read(), 7);
+}
+
+#[test]
+fn inspect_sees_every_byte_once() {
+    let mut seen = Vec::new();
+    {
+        let rd = io::BufReader::with_capacity(3, Cursor::new(b"abc\ndef\nghi".to_vec()));
+        let mut rd = inspect(rd, |data: &[u8]| seen.extend_from_slice(data));
+
+        assert_eq!(rd.fill_buf().unwrap(), b"abc");
+        assert_eq!(rd.fill_buf().unwrap(), b"abc");
+        rd.consume(1);
+
+        let (rd, line) = read_until(rd, b'\n', Vec::new()).wait().unwrap();
+        assert_eq!(line, b"bc\n");
+
+        let (_, rest) = read_to_end(rd, Vec::new()).wait().unwrap();
+        assert_eq!(rest, b"def\nghi");
+    }
+    assert_eq!(seen, b"abc\ndef\nghi");
+}
+
+#[test]
+fn split_once_head_and_body() {
+    let mut rd = io::BufReader::with_capacity(2, Cursor::new(b"len=3;abcxyz".to_vec()));
+
+    let (_, head) = split_once(&mut rd, b';').wait().unwrap();
+    assert_eq!(head, b"len=3");
+
+    let (_, body) = read_exact(&mut rd, [0; 3]).wait().unwrap();
+    assert_eq!(&body, b"abc");
+
+    let err = split_once(rd, b';').wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn windows_overlap() {
+    let rd = io::BufReader::with_capacity(2, Cursor::new(b"abcde".to_vec()));
+
+    let all = windows(rd, 3).collect().wait().unwrap();
+    assert_eq!(all, vec![b"abc".to_vec(), b"bcd".to_vec(), b"cde".to_vec()]);
+
+    let rd = Cursor::new(b"ab".to_vec());
+    assert!(windows(rd, 3).collect().wait().unwrap().is_empty());
+}
+
+#[test]
+fn read_while_stops_before_mismatch() {
+    let rd = io::BufReader::with_capacity(2, Cursor::new(b"12345abc".to_vec()));
+
+    let (rd, digits) = read_while(rd, |b| b.is_ascii_digit(), Vec::new()).wait().unwrap();
+    assert_eq!(digits, b"12345");
+
+    let (rd, none) = read_while(rd, |b| b.is_ascii_digit(), Vec::new()).wait().unwrap();
+    assert!(none.is_empty());
+
+    let (_, rest) = read_while(rd, |_| true, b"x".to_vec()).wait().unwrap();
+    assert_eq!(rest, b"xabc");
+}
+
+#[test]
+fn skip_bytes_discards_exactly_n() {
+    let rd = io::BufReader::with_capacity(2, Cursor::new(b"\0\0\0record".to_vec()));
+
+    let rd = skip_bytes(rd, 3).wait().unwrap();
+    let (rd, buf) = read_exact(rd, [0; 6]).wait().unwrap();
+    assert_eq!(&buf, b"record");
+
+    let rd = skip_bytes(rd, 0).wait().unwrap();
+    let err = skip_bytes(rd, 1).wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn limited_read_fragments_reads() {
+    let mut rd = LimitedRead::new(Cursor::new(b"abcdefg".to_vec()), 3);
+    let mut buf = [0; 8];
+    assert_eq!(rd.read(&mut buf).unwrap(), 3);
+    assert_eq!(rd.fill_buf().unwrap(), b"def");
+    rd.consume(1);
+    assert_eq!(rd.fill_buf().unwrap(), b"efg");
+
+    let (_, buf, _) = read_until_any(rd, b"z", Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"efg");
+}
+
+#[test]
+fn slice_buf_read() {
+    let data = b"key=value\nrest";
+
+    let (rd, key) = split_once(&data[..], b'=').wait().unwrap();
+    assert_eq!(key, b"key");
+
+    let (rd, value) = read_until(rd, b'\n', Vec::new()).wait().unwrap();
+    assert_eq!(value, b"value\n");
+    assert_eq!(rd, b"rest");
+}
+
+#[test]
+fn read_n_bytes_allocates_exact() {
+    let rd = io::BufReader::with_capacity(2, Cursor::new(b"headerbody".to_vec()));
+
+    let (rd, header) = read_n_bytes(rd, 6).wait().unwrap();
+    assert_eq!(header, b"header");
+
+    let err = read_n_bytes(rd, 5).wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn read_n_bytes_limit() {
+    let rd = Cursor::new(b"abc".to_vec());
+    let err = read_n_bytes(rd, 3).max_len(2).wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let rd = Cursor::new(b"abc".to_vec());
+    let (_, buf) = read_n_bytes(rd, 2).max_len(2).wait().unwrap();
+    assert_eq!(buf, b"ab");
+}
+
+#[test]
+fn lines_bytes_strips_line_endings() {
+    let data = b"one\r\n\xfftwo\n\nlast".to_vec();
+    let rd = io::BufReader::with_capacity(3, Cursor::new(data));
+    let lines = lines_bytes(rd).collect().wait().unwrap();
+    assert_eq!(lines, vec![b"one".to_vec(), b"\xfftwo".to_vec(), vec![], b"last".to_vec()]);
+}
+
+#[test]
+fn tee_read_copies_data() {
+    let rd = io::BufReader::with_capacity(3, Cursor::new(b"one\ntwo\n".to_vec()));
+    let mut rd = TeeRead::new(rd, Cursor::new(Vec::new()));
+
+    let mut buf = [0; 2];
+    rd.read_exact(&mut buf).unwrap();
+    let (rd, line) = read_until(rd, b'\n', Vec::new()).wait().unwrap();
+    assert_eq!(line, b"e\n");
+    let (mut rd, rest) = read_to_end(rd, Vec::new()).wait().unwrap();
+    assert_eq!(rest, b"two\n");
+
+    assert!(rd.poll_flush().unwrap().is_ready());
+    let (_, copy) = rd.into_inner();
+    assert_eq!(copy.into_inner(), b"one\ntwo\n");
+}
+
+struct Broken;
+
+impl Write for Broken {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::Other, "broken"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn tee_read_writer_errors() {
+    let mut rd = TeeRead::new(Cursor::new(b"data".to_vec()), AllowStdIo::new(Broken));
+    assert!(!rd.ignores_errors());
+    let err = rd.fill_buf().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+
+    rd.set_ignore_errors(true);
+    let (_, buf) = read_to_end(rd, Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"data");
+}
+
+#[test]
+fn parse_lines_from_str() {
+    let rd = Cursor::new(b"1\n22\r\n333".to_vec());
+    let values = parse_lines::<_, u32>(rd).collect().wait().unwrap();
+    assert_eq!(values, vec![1, 22, 333]);
+
+    let rd = Cursor::new(b"1\nx\n".to_vec());
+    let err = parse_lines::<_, u32>(rd).collect().wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn scan_folds_chunks() {
+    let rd = LimitedRead::new(Cursor::new(b"abcdefghij".to_vec()), 3);
+    let sums = scan(rd, 0, |total: &mut usize, chunk: &[u8]| {
+        *total += chunk.len();
+        if *total >= 4 {
+            Some(::std::mem::replace(total, 0))
+        } else {
+            None
+        }
+    });
+    assert_eq!(sums.collect().wait().unwrap(), vec![6, 4]);
+}
@@ -0,0 +1,118 @@
+extern crate tokio_io;
+extern crate futures;
+extern crate bytes;
+
+use tokio_io::io::{read_to_end, BufReader};
+use futures::{Async, Future};
+use bytes::BytesMut;
+
+use std::cmp;
+use std::io::{self, BufRead, Cursor, Read, Seek, SeekFrom};
+use tokio_io::AsyncRead;
+
+// Returns each chunk from separate reads, with a `WouldBlock` error in
+// between.
+struct Chunks {
+    chunks: Vec<&'static [u8]>,
+    blocked: bool,
+}
+
+impl Read for Chunks {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.chunks.is_empty() {
+            return Ok(0);
+        }
+        self.blocked = !self.blocked;
+        if self.blocked {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        let n = cmp::min(buf.len(), self.chunks[0].len());
+        buf[..n].copy_from_slice(&self.chunks[0][..n]);
+        self.chunks[0] = &self.chunks[0][n..];
+        if self.chunks[0].is_empty() {
+            self.chunks.remove(0);
+        }
+        Ok(n)
+    }
+}
+
+impl AsyncRead for Chunks {}
+
+#[test]
+fn peek_does_not_consume() {
+    let mut rd = BufReader::with_capacity(2, Cursor::new(b"hello world".to_vec()));
+
+    assert_eq!(rd.peek(5).wait().unwrap(), b"hello");
+    assert_eq!(rd.peek(3).wait().unwrap(), b"hel");
+
+    let (_, buf) = read_to_end(rd, Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"hello world");
+}
+
+#[test]
+fn peek_past_eof() {
+    let mut rd = BufReader::new(Cursor::new(b"abc".to_vec()));
+    assert_eq!(rd.peek(10).wait().unwrap(), b"abc");
+}
+
+#[test]
+fn poll_fill_buf_at_least() {
+    let chunks = Chunks { chunks: vec![b"ab", b"cd", b"ef"], blocked: false };
+    let mut rd = BufReader::with_capacity(2, chunks);
+
+    assert_eq!(rd.poll_fill_buf_at_least(3).unwrap(), Async::NotReady);
+    assert_eq!(rd.poll_fill_buf_at_least(3).unwrap(), Async::NotReady);
+    assert_eq!(rd.buffer(), b"ab");
+    assert_eq!(rd.poll_fill_buf_at_least(3).unwrap(), Async::Ready(&b"abc"[..]));
+    rd.consume(2);
+    assert_eq!(rd.poll_fill_buf_at_least(1).unwrap(), Async::Ready(&b"c"[..]));
+    assert_eq!(rd.poll_fill_buf_at_least(5).unwrap(), Async::NotReady);
+    assert_eq!(rd.poll_fill_buf_at_least(5).unwrap(), Async::NotReady);
+    assert_eq!(rd.buffer(), b"cd");
+    assert_eq!(rd.poll_fill_buf_at_least(5).unwrap(), Async::Ready(&b"cdef"[..]));
+}
+
+#[test]
+fn reads_at_most_capacity() {
+    let mut rd = BufReader::with_capacity(4, Cursor::new(b"hello world".to_vec()));
+    assert_eq!(rd.capacity(), 4);
+
+    assert_eq!(rd.fill_buf().unwrap(), b"hell");
+    rd.consume(4);
+    assert_eq!(rd.fill_buf().unwrap(), b"o wo");
+}
+
+#[test]
+fn seek() {
+    let mut rd = BufReader::with_capacity(4, Cursor::new(b"hello world".to_vec()));
+
+    assert_eq!(rd.fill_buf().unwrap(), b"hell");
+    rd.consume(1);
+    assert_eq!(rd.seek(SeekFrom::Current(1)).unwrap(), 2);
+    assert_eq!(rd.fill_buf().unwrap(), b"llo ");
+
+    rd.seek_relative(3).unwrap();
+    assert_eq!(rd.get_ref().position(), 6);
+    assert_eq!(rd.fill_buf().unwrap(), b" ");
+
+    rd.seek_relative(2).unwrap();
+    assert_eq!(rd.fill_buf().unwrap(), b"orld");
+
+    assert_eq!(rd.seek(SeekFrom::Start(0)).unwrap(), 0);
+    assert_eq!(rd.fill_buf().unwrap(), b"hell");
+}
+
+#[test]
+fn from_parts_uses_given_buffer() {
+    let mut buf = BytesMut::with_capacity(64);
+    buf.extend_from_slice(b"ab");
+    let rd = BufReader::from_parts(Cursor::new(b"cdef".to_vec()), buf);
+    assert_eq!(rd.capacity(), 64);
+    assert_eq!(rd.buffer(), b"ab");
+
+    let (rd, data) = read_to_end(rd, Vec::new()).wait().unwrap();
+    assert_eq!(data, b"abcdef");
+    let (_, buf) = rd.into_parts();
+    assert!(buf.is_empty());
+    assert!(buf.capacity() >= 64);
+}
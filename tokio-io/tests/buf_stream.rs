@@ -0,0 +1,28 @@
+extern crate tokio_io;
+extern crate futures;
+
+use tokio_io::io::{flush, read_until, BufStream};
+use futures::Future;
+
+use std::io::{Cursor, Write};
+
+#[test]
+fn buffers_both_directions() {
+    let stream = BufStream::with_capacity(4, 16, Cursor::new(b"ping\nrest".to_vec()));
+
+    let (mut stream, line) = read_until(stream, b'\n', Vec::new()).wait().unwrap();
+    assert_eq!(line, b"ping\n");
+
+    assert_eq!(stream.read_buffer(), b"res");
+
+    stream.write_all(b"pong").unwrap();
+    assert_eq!(stream.write_buffer(), b"pong");
+    assert_eq!(stream.get_ref().get_ref(), b"ping\nrest");
+
+    let stream = flush(stream).wait().unwrap();
+    let (inner, read_buf, write_buf) = stream.into_parts();
+    assert!(write_buf.is_empty());
+    assert_eq!(read_buf, b"res"[..]);
+    // The cursor writes at the position the reader left it at
+    assert_eq!(inner.get_ref(), b"ping\nrespong");
+}
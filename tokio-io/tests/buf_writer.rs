@@ -0,0 +1,89 @@
+extern crate tokio_io;
+extern crate bytes;
+extern crate futures;
+
+use tokio_io::AsyncBufWrite;
+use tokio_io::io::BufWriter;
+use bytes::BufMut;
+use futures::{Async, Future};
+
+use std::io::{Cursor, Write};
+
+#[test]
+fn buffers_until_flushed() {
+    let mut writer = BufWriter::with_capacity(16, Cursor::new(Vec::new()));
+
+    writer.write_all(b"hello").unwrap();
+    writer.get_buf_mut().put_slice(b" world");
+    assert_eq!(writer.buffer(), b"hello world");
+    assert!(writer.get_ref().get_ref().is_empty());
+
+    let writer = writer.flush_buf().wait().unwrap();
+    assert!(writer.buffer().is_empty());
+    assert_eq!(writer.get_ref().get_ref(), b"hello world");
+}
+
+#[test]
+fn large_writes_bypass_buffer() {
+    let mut writer = BufWriter::with_capacity(4, Cursor::new(Vec::new()));
+
+    writer.write_all(b"ab").unwrap();
+    writer.write_all(b"cdefgh").unwrap();
+    assert!(writer.buffer().is_empty());
+    assert_eq!(writer.get_ref().get_ref(), b"abcdefgh");
+}
+
+#[test]
+fn write_into_lent_buffer() {
+    let mut writer = BufWriter::with_capacity(4, Cursor::new(Vec::new()));
+
+    writer.write_all(b"a").unwrap();
+    match writer.poll_write_buf().unwrap() {
+        Async::Ready(buf) => {
+            assert_eq!(buf.len(), 3);
+            buf[..2].copy_from_slice(b"bc");
+        }
+        Async::NotReady => panic!("not ready"),
+    }
+    writer.advance(2);
+    assert_eq!(writer.buffer(), b"abc");
+
+    match writer.poll_write_buf().unwrap() {
+        Async::Ready(buf) => {
+            assert_eq!(buf, b"\0");
+            buf[0] = b'd';
+        }
+        Async::NotReady => panic!("not ready"),
+    }
+    writer.advance(1);
+    let len = match writer.poll_write_buf().unwrap() {
+        Async::Ready(buf) => buf.len(),
+        Async::NotReady => panic!("not ready"),
+    };
+    assert_eq!(len, 4);
+    assert!(writer.buffer().is_empty());
+    assert_eq!(writer.get_ref().get_ref(), b"abcd");
+}
+
+#[test]
+#[should_panic]
+fn advance_past_buffer() {
+    let mut writer = BufWriter::with_capacity(4, Cursor::new(Vec::new()));
+    writer.advance(5);
+}
+
+#[test]
+#[should_panic]
+fn advance_without_poll() {
+    let mut writer = BufWriter::with_capacity(4, Cursor::new(Vec::new()));
+    writer.advance(3);
+}
+
+#[test]
+#[should_panic]
+fn advance_twice_after_poll() {
+    let mut writer = BufWriter::with_capacity(4, Cursor::new(Vec::new()));
+    assert!(writer.poll_write_buf().unwrap().is_ready());
+    writer.advance(1);
+    writer.advance(1);
+}
@@ -2,7 +2,12 @@ extern crate tokio_io;
 extern crate bytes;
 
 use bytes::{BytesMut, Bytes, BufMut};
-use tokio_io::codec::{BytesCodec, LinesCodec, Decoder, Encoder};
+use tokio_io::codec::{BytesCodec, BytesCodecError, FrozenBytesCodec, HeartbeatCodec,
+                      LinesCodec, LinesCodecError, MultipartDecoder, MultipartError,
+                      NullDelimitedCodec, Phase, SplitCodec, VarIntCodec, Decoder, Encoder};
+use tokio_io::codec::length_delimited::LengthDelimitedCodec;
+
+use std::io;
 
 #[test]
 fn bytes_decoder() {
@@ -36,6 +41,40 @@ fn bytes_encoder() {
     codec.encode(Bytes::from_static(&[0; INITIAL_CAPACITY + 1]), &mut buf).unwrap();
 }
 
+#[test]
+fn bytes_decoder_max_size() {
+    let mut codec = BytesCodec::new().with_max_size(3);
+    let buf = &mut BytesMut::new();
+    buf.put_slice(b"abc");
+    assert_eq!("abc", codec.decode(buf).unwrap().unwrap());
+
+    buf.put_slice(b"abcd");
+    let err = codec.decode(buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    let inner = err.get_ref().unwrap().downcast_ref::<BytesCodecError>();
+    assert_eq!(inner, Some(&BytesCodecError::TooLarge(4)));
+    assert!(buf.is_empty());
+
+    let mut codec = FrozenBytesCodec::new().with_max_size(3);
+    buf.put_slice(b"abcd");
+    assert!(codec.decode(buf).is_err());
+}
+
+#[test]
+fn frozen_bytes_decoder() {
+    let mut codec = FrozenBytesCodec::new();
+    let buf = &mut BytesMut::new();
+    buf.put_slice(b"abc");
+    let frame: Bytes = codec.decode(buf).unwrap().unwrap();
+    assert_eq!("abc", frame);
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put_slice(b"a");
+    assert_eq!("a", codec.decode(buf).unwrap().unwrap());
+
+    codec.encode(frame, buf).unwrap();
+    assert_eq!("abc", buf);
+}
+
 #[test]
 fn lines_decoder() {
     let mut codec = LinesCodec::new();
@@ -55,6 +94,68 @@ fn lines_decoder() {
     assert_eq!(None, codec.decode_eof(buf).unwrap());
 }
 
+#[test]
+fn lines_decoder_max_length() {
+    const MAX_LENGTH: usize = 6;
+
+    let mut codec = LinesCodec::new_with_max_length(MAX_LENGTH);
+    let buf = &mut BytesMut::new();
+
+    buf.reserve(200);
+    buf.put("line 1 is too long\nline 2\nline 3\r\nline 4\n\r\n");
+
+    match codec.decode(buf) {
+        Err(LinesCodecError::MaxLineLengthExceeded) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    let line = codec.decode(buf).unwrap().unwrap();
+    assert!(line.len() <= MAX_LENGTH, "{:?}.len() <= {:?}", line, MAX_LENGTH);
+    assert_eq!("line 2", line);
+
+    // The "\r" of "line 3\r\n" doesn't count towards the limit
+    assert_eq!("line 3", codec.decode(buf).unwrap().unwrap());
+
+    let line = codec.decode(buf).unwrap().unwrap();
+    assert_eq!("line 4", line);
+
+    assert_eq!("", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!(None, codec.decode_eof(buf).unwrap());
+
+    // Partial lines are kept across calls until the limit is hit.
+    buf.put("line ");
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put("5\n");
+    assert_eq!("line 5", codec.decode(buf).unwrap().unwrap());
+}
+
+#[test]
+fn lines_decoder_max_length_crlf() {
+    let mut codec = LinesCodec::new_with_max_length(6);
+    let buf = &mut BytesMut::new();
+
+    // A line at the limit, with its "\r\n" arriving separately
+    buf.put("line 1\r");
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put("\nline 22\r\nline 3\rx\n");
+    assert_eq!("line 1", codec.decode(buf).unwrap().unwrap());
+
+    // One character over the limit
+    match codec.decode(buf) {
+        Err(LinesCodecError::MaxLineLengthExceeded) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    // A "\r" which isn't right before the "\n" counts
+    match codec.decode(buf) {
+        Err(LinesCodecError::MaxLineLengthExceeded) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert!(buf.is_empty());
+}
+
 #[test]
 fn lines_encoder() {
     let mut codec = BytesCodec::new();
@@ -74,3 +175,331 @@ fn lines_encoder() {
     let mut buf = BytesMut::with_capacity(INITIAL_CAPACITY);
     codec.encode(Bytes::from_static(&[b'a'; INITIAL_CAPACITY + 1]), &mut buf).unwrap();
 }
+
+#[test]
+fn map_decoder() {
+    let mut codec = LinesCodec::new().map(|line| line.parse::<u32>().unwrap());
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("1\n22\n333");
+    assert_eq!(1, codec.decode(buf).unwrap().unwrap());
+    assert_eq!(22, codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!(333, codec.decode_eof(buf).unwrap().unwrap());
+
+    codec.encode("4".to_string(), buf).unwrap();
+    assert_eq!("4\n", buf);
+}
+
+#[test]
+fn map_length_delimited_payloads() {
+    let mut codec = LengthDelimitedCodec::new().map(|frame| frame.freeze());
+    let buf = &mut BytesMut::new();
+    codec.encode(Bytes::from("abc"), buf).unwrap();
+    let frame: Bytes = codec.decode(buf).unwrap().unwrap();
+    assert_eq!(frame, "abc");
+}
+
+#[derive(Debug)]
+enum ProtocolError {
+    Codec(io::ErrorKind),
+    Io,
+}
+
+impl From<io::Error> for ProtocolError {
+    fn from(_: io::Error) -> ProtocolError {
+        ProtocolError::Io
+    }
+}
+
+#[test]
+fn map_err_codec() {
+    let mut inner = LengthDelimitedCodec::new();
+    inner.set_max_frame_length(2);
+    let mut codec = inner.map_err(|e: io::Error| ProtocolError::Codec(e.kind()));
+    let buf = &mut BytesMut::new();
+    match codec.encode(Bytes::from("abc"), buf) {
+        Err(ProtocolError::Codec(io::ErrorKind::InvalidInput)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    codec.encode(Bytes::from("ab"), buf).unwrap();
+    assert_eq!(codec.decode(buf).unwrap().unwrap(), "ab");
+
+    buf.put("\x00\x00\x00\x03abc");
+    match codec.decode(buf) {
+        Err(ProtocolError::Codec(io::ErrorKind::InvalidData)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn encode_to_bytes() {
+    let mut codec = LengthDelimitedCodec::new();
+    let bytes = codec.encode_to_bytes(Bytes::from("abc")).unwrap();
+    assert_eq!(bytes, &b"\x00\x00\x00\x03abc"[..]);
+}
+
+#[test]
+fn encode_all() {
+    let mut codec = LengthDelimitedCodec::new();
+    let mut buf = BytesMut::new();
+    buf.reserve(1);
+    buf.put_u8(b'x');
+    let n = codec.encode_all(vec![Bytes::from("ab"), Bytes::from("")], &mut buf).unwrap();
+    assert_eq!(n, 10);
+    assert_eq!(buf, &b"x\x00\x00\x00\x02ab\x00\x00\x00\x00"[..]);
+
+    codec.set_max_frame_length(1);
+    let mut buf = BytesMut::new();
+    let items = vec![Bytes::from("a"), Bytes::from("bc"), Bytes::from("d")];
+    let err = codec.encode_all(items, &mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(buf, &b"\x00\x00\x00\x01a"[..]);
+}
+
+#[test]
+fn decode_from_bytes() {
+    let mut codec = LengthDelimitedCodec::new();
+    let frame = codec.decode_from_bytes(Bytes::from(&b"\x00\x00\x00\x03abc"[..])).unwrap();
+    assert_eq!(frame.unwrap(), "abc");
+
+    let mut codec = LinesCodec::new();
+    assert_eq!(None, codec.decode_from_bytes(Bytes::from("partial")).unwrap());
+}
+
+#[test]
+fn multipart_decoder() {
+    let mut codec = MultipartDecoder::new(b"xyz");
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("prologue\r\n--xyz\r\nContent-Type: text/plain\r\n\r\nfirst");
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put(" part\r\n--xyz  \r\n\r\nsecond\r\n--xy");
+    assert_eq!("first part", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put("z--\r\nepilogue");
+    assert_eq!("second", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert!(buf.is_empty());
+    assert!(codec.decode_eof(buf).unwrap().is_none());
+}
+
+#[test]
+fn multipart_decoder_boundary_at_start() {
+    let mut codec = MultipartDecoder::new(b"xyz");
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("--x");
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put("yz\r\n\r\nonly\r\n--xyz--");
+    assert_eq!("only", codec.decode(buf).unwrap().unwrap());
+    assert!(codec.decode_eof(buf).unwrap().is_none());
+}
+
+#[test]
+fn multipart_decoder_missing_boundary() {
+    let mut codec = MultipartDecoder::new(b"xyz");
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("--xyz\r\n\r\ntruncated");
+    match codec.decode_eof(buf) {
+        Err(MultipartError::MissingBoundary) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn and_then_decoder() {
+    // A header line holding the length of the body which follows it
+    struct Fixed(usize);
+
+    impl Decoder for Fixed {
+        type Item = BytesMut;
+        type Error = io::Error;
+
+        fn decode(&mut self, buf: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+            if buf.len() < self.0 {
+                return Ok(None);
+            }
+            Ok(Some(buf.split_to(self.0)))
+        }
+    }
+
+    let mut codec = LinesCodec::new().and_then(|header: &String| {
+        Fixed(header.parse().unwrap())
+    });
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("3\nab");
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put("c2\nde");
+    let (header, body) = codec.decode(buf).unwrap().unwrap();
+    assert_eq!(header, "3");
+    assert_eq!(body, "abc");
+    let (header, body) = codec.decode(buf).unwrap().unwrap();
+    assert_eq!(header, "2");
+    assert_eq!(body, "de");
+
+    buf.put("1\n");
+    assert!(codec.decode_eof(buf).is_err());
+}
+
+#[test]
+fn null_delimited_decoder() {
+    let mut codec = NullDelimitedCodec::new();
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("abc\0\0de");
+    assert_eq!("abc", codec.decode(buf).unwrap().unwrap());
+    assert_eq!("", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put("f\0");
+    assert_eq!("def", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+}
+
+#[test]
+fn null_delimited_decoder_limit() {
+    let mut codec = NullDelimitedCodec::new_with_limit(3);
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("abcdef\0ab\0");
+    assert!(codec.decode(buf).is_err());
+    assert_eq!("ab", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+}
+
+#[test]
+fn null_delimited_encoder() {
+    let mut codec = NullDelimitedCodec::new();
+    let mut buf = BytesMut::new();
+    codec.encode(Bytes::from("abc"), &mut buf).unwrap();
+    codec.encode(Bytes::from(""), &mut buf).unwrap();
+    assert_eq!("abc\0\0", buf);
+}
+
+#[test]
+fn heartbeat_decoder() {
+    let mut codec = HeartbeatCodec::new(b"PING");
+    let buf = &mut BytesMut::new();
+    buf.put_slice(b"PINGPI");
+    assert_eq!(Some(()), codec.decode(buf).unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put_slice(b"NGPONG");
+    assert_eq!(Some(()), codec.decode(buf).unwrap());
+    let err = codec.decode(buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn heartbeat_encoder() {
+    let mut codec = HeartbeatCodec::new(b"\xc0\x00");
+    let mut buf = BytesMut::new();
+    codec.encode((), &mut buf).unwrap();
+    codec.encode((), &mut buf).unwrap();
+    assert_eq!(&buf[..], b"\xc0\x00\xc0\x00");
+}
+
+#[test]
+fn varint_decoder() {
+    let mut codec = VarIntCodec::new();
+    let buf = &mut BytesMut::new();
+    buf.put_slice(b"\x03ab");
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put_slice(b"c\x00\xac");
+    assert_eq!("abc", codec.decode(buf).unwrap().unwrap());
+    assert_eq!("", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.reserve(302);
+    buf.put_slice(b"\x02");
+    buf.put_slice(&[b'x'; 300][..]);
+    assert_eq!(&[b'x'; 300][..], &codec.decode(buf).unwrap().unwrap()[..]);
+    assert!(buf.is_empty());
+
+    buf.reserve(10);
+    buf.put_slice(&[0xff; 10][..]);
+    assert_eq!(codec.decode(buf).unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn varint_decoder_limit() {
+    let mut codec = VarIntCodec::new();
+    codec.set_max_frame_length(127);
+    let buf = &mut BytesMut::new();
+    buf.put_slice(b"\x80\x01");
+    assert_eq!(codec.decode(buf).unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn varint_zigzag_decoder() {
+    let mut codec = VarIntCodec::new_zigzag();
+    let buf = &mut BytesMut::new();
+    buf.put_slice(b"\x06abc");
+    assert_eq!("abc", codec.decode(buf).unwrap().unwrap());
+    buf.put_slice(b"\x05abc");
+    assert_eq!(codec.decode(buf).unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn varint_encoder() {
+    let mut codec = VarIntCodec::new();
+    let mut buf = BytesMut::new();
+    codec.encode(Bytes::from("abc"), &mut buf).unwrap();
+    assert_eq!(&buf[..], b"\x03abc");
+
+    let mut buf = BytesMut::new();
+    codec.encode(Bytes::from(vec![0; 300]), &mut buf).unwrap();
+    assert_eq!(&buf[..2], b"\xac\x02");
+    assert_eq!(buf.len(), 302);
+
+    let mut codec = VarIntCodec::new_zigzag();
+    let mut buf = BytesMut::new();
+    codec.encode(Bytes::from("abc"), &mut buf).unwrap();
+    assert_eq!(&buf[..], b"\x06abc");
+
+    codec.set_max_frame_length(2);
+    let err = codec.encode(Bytes::from("abc"), &mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn split_codec_switches_once_drained() {
+    let second = NullDelimitedCodec::new().map(|b| String::from_utf8(b.to_vec()).unwrap());
+    let mut codec = SplitCodec::new(LinesCodec::new(), second);
+    let buf = &mut BytesMut::new();
+    buf.reserve(64);
+
+    buf.put_slice(b"HELLO\nWOR");
+    assert_eq!("HELLO", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!(Phase::First, codec.phase());
+    buf.put_slice(b"LD\n");
+    assert_eq!("WORLD", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(Phase::First, codec.phase());
+
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!(Phase::Second, codec.phase());
+    buf.put_slice(b"a\nb\0c");
+    assert_eq!("a\nb", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!(Phase::Second, codec.phase());
+}
+
+#[test]
+fn boxed_decoders() {
+    let mut decoders: Vec<Box<Decoder<Item = Bytes, Error = io::Error>>> = vec![
+        Box::new(NullDelimitedCodec::new()),
+        Box::new(LengthDelimitedCodec::new().map(|frame| frame.freeze())),
+    ];
+    let buf = &mut BytesMut::new();
+    buf.reserve(64);
+
+    buf.put_slice(b"abc\0");
+    assert_eq!("abc", decoders[0].decode(buf).unwrap().unwrap());
+    buf.put_slice(b"\x00\x00\x00\x02de");
+    assert_eq!("de", decoders[1].decode(buf).unwrap().unwrap());
+
+    let mut codec = LinesCodec::new().with_max_buf_size(16);
+    let boxed: Box<Decoder<Item = String, Error = LinesCodecError>> = Box::new(&mut codec);
+    assert_eq!(boxed.max_buf_size(), 16);
+}
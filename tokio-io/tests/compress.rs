@@ -0,0 +1,44 @@
+#![cfg(feature = "flate2")]
+
+extern crate tokio_io;
+extern crate futures;
+
+use tokio_io::io::{pipe, read_to_end, shutdown, write_all, BufReader};
+use tokio_io::io::compress::{GzipDecoder, GzipEncoder};
+use futures::Future;
+
+use std::io::Cursor;
+
+#[test]
+fn gzip_round_trip_through_pipe() {
+    let data: Vec<u8> = (0..100_000u64).map(|i| (i * i % 251) as u8).collect();
+    let (wr, rd) = pipe(64);
+
+    let write = write_all(GzipEncoder::new(wr), data.clone())
+        .and_then(|(wr, _)| shutdown(wr));
+    let read = read_to_end(GzipDecoder::new(rd), Vec::new());
+    let (_, (_, decompressed)) = write.join(read).wait().unwrap();
+    assert_eq!(decompressed, data);
+}
+
+#[test]
+fn gzip_decoder_leaves_trailing_data() {
+    let wr = write_all(GzipEncoder::new(Cursor::new(Vec::new())), b"hello")
+        .and_then(|(wr, _)| shutdown(wr))
+        .wait()
+        .unwrap();
+    let mut compressed = wr.get_ref().get_ref().clone();
+    compressed.extend_from_slice(b"rest");
+
+    let rd = BufReader::new(Cursor::new(compressed));
+    let (rd, buf) = read_to_end(GzipDecoder::new(rd), Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"hello");
+    let (_, rest) = read_to_end(rd.into_inner(), Vec::new()).wait().unwrap();
+    assert_eq!(rest, b"rest");
+}
+
+#[test]
+fn gzip_decoder_rejects_garbage() {
+    let rd = Cursor::new(b"definitely not gzip".to_vec());
+    assert!(read_to_end(GzipDecoder::new(rd), Vec::new()).wait().is_err());
+}
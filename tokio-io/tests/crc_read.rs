@@ -0,0 +1,48 @@
+#![cfg(feature = "crc32")]
+
+extern crate tokio_io;
+extern crate futures;
+
+use tokio_io::io::{crc_read, read_until};
+use futures::Future;
+
+use std::io::{self, BufRead, Cursor};
+
+// "hello\nworld" followed by its CRC32
+const DATA: &'static [u8] = b"hello\nworld\xab\x7d\x37\xd5";
+
+#[test]
+fn crc_read_hides_checksum() {
+    let rd = io::BufReader::with_capacity(3, Cursor::new(DATA));
+    let rd = crc_read(rd);
+
+    let (mut rd, line) = read_until(rd, b'\n', Vec::new()).wait().unwrap();
+    assert_eq!(line, b"hello\n");
+    let rest = rd.fill_buf().unwrap().to_vec();
+    assert!(b"world".starts_with(&rest));
+
+    let (mut rd, line) = read_until(rd, b'\n', Vec::new()).wait().unwrap();
+    assert_eq!(line, b"world");
+    assert_eq!(rd.fill_buf().unwrap(), b"");
+
+    let (_, crc) = rd.verify().wait().unwrap();
+    assert_eq!(crc, 0xab7d37d5);
+}
+
+#[test]
+fn crc_verify_skips_unread_data() {
+    let rd = crc_read(Cursor::new(DATA));
+    let (_, crc) = rd.verify().wait().unwrap();
+    assert_eq!(crc, 0xab7d37d5);
+}
+
+#[test]
+fn crc_mismatch() {
+    let rd = crc_read(Cursor::new(&b"hello\nworle\xab\x7d\x37\xd5"[..]));
+    let err = rd.verify().wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let rd = crc_read(Cursor::new(&b"abc"[..]));
+    let err = rd.verify().wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
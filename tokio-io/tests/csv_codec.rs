@@ -0,0 +1,57 @@
+#![cfg(feature = "csv")]
+
+extern crate tokio_io;
+extern crate bytes;
+
+use bytes::{BytesMut, BufMut};
+use tokio_io::codec::{CsvCodec, CsvCodecError, Decoder, Encoder};
+
+fn fields(fields: &[&str]) -> Vec<String> {
+    fields.iter().map(|f| f.to_string()).collect()
+}
+
+#[test]
+fn csv_decoder() {
+    let mut codec = CsvCodec::new();
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("a,b,c\r\n\n\"x, y\",\"say \"\"hi\"\"\",\"multi\nline\"\n\"open");
+    assert_eq!(fields(&["a", "b", "c"]), codec.decode(buf).unwrap().unwrap());
+    assert_eq!(fields(&["x, y", "say \"hi\"", "multi\nline"]),
+               codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put(" quote\n\",last");
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!(fields(&["open quote\n", "last"]), codec.decode_eof(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode_eof(buf).unwrap());
+}
+
+#[test]
+fn csv_decoder_quote_inside_unquoted_field() {
+    let mut codec = CsvCodec::new();
+    let buf = &mut BytesMut::new();
+    buf.put("a\"b,c\nd,e\n");
+    assert_eq!(fields(&["a\"b", "c"]), codec.decode(buf).unwrap().unwrap());
+    assert_eq!(fields(&["d", "e"]), codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+}
+
+#[test]
+fn csv_decoder_invalid_utf8() {
+    let mut codec = CsvCodec::new();
+    let buf = &mut BytesMut::new();
+    buf.put(&b"\xff,a\n"[..]);
+    match codec.decode(buf) {
+        Err(CsvCodecError::ParseError(_)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn csv_encoder() {
+    let mut codec = CsvCodec::new();
+    let mut buf = BytesMut::new();
+    codec.encode(fields(&["a", "b, c", "\"q\""]), &mut buf).unwrap();
+    codec.encode(fields(&["line\nbreak"]), &mut buf).unwrap();
+    assert_eq!(&buf[..], &b"a,\"b, c\",\"\"\"q\"\"\"\r\n\"line\nbreak\"\r\n"[..]);
+}
@@ -0,0 +1,113 @@
+extern crate tokio_io;
+extern crate futures;
+
+use tokio_io::AsyncWrite;
+use tokio_io::io::{copy_bidirectional, duplex, read_exact, read_to_end, read_until, shutdown,
+                   split, write_all};
+use futures::Future;
+
+use std::io::{self, Write};
+
+#[test]
+fn write_then_read() {
+    let (a, b) = duplex(64);
+    let (a, _) = write_all(a, b"ping").wait().unwrap();
+    let (b, buf) = read_exact(b, [0; 4]).wait().unwrap();
+    assert_eq!(&buf, b"ping");
+
+    let (_b, _) = write_all(b, b"pong").wait().unwrap();
+    let (_a, buf) = read_exact(a, [0; 4]).wait().unwrap();
+    assert_eq!(&buf, b"pong");
+}
+
+#[test]
+fn writes_block_until_read() {
+    let (a, b) = duplex(3);
+    let data = (0..100).collect::<Vec<u8>>();
+    let write = write_all(a, data.clone());
+    let read = read_exact(b, vec![0; 100]);
+    let (_, (_, buf)) = write.join(read).wait().unwrap();
+    assert_eq!(buf, data);
+}
+
+#[test]
+fn buf_read() {
+    let (a, b) = duplex(64);
+    let (_a, _) = write_all(a, b"hello\nworld\n").wait().unwrap();
+    let (b, line) = read_until(b, b'\n', Vec::new()).wait().unwrap();
+    assert_eq!(line, b"hello\n");
+    let (_b, line) = read_until(b, b'\n', Vec::new()).wait().unwrap();
+    assert_eq!(line, b"world\n");
+}
+
+#[test]
+fn shutdown_signals_eof() {
+    let (a, b) = duplex(64);
+    let (a, _) = write_all(a, b"bye").wait().unwrap();
+    let _a = shutdown(a).wait().unwrap();
+    let (_b, buf) = read_to_end(b, Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"bye");
+}
+
+#[test]
+fn drop_signals_eof() {
+    let (a, b) = duplex(64);
+    let (a, _) = write_all(a, b"bye").wait().unwrap();
+    drop(a);
+    let (_b, buf) = read_to_end(b, Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"bye");
+}
+
+#[test]
+fn write_after_drop_fails() {
+    let (mut a, b) = duplex(64);
+    drop(b);
+    let err = a.write(b"hello").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    assert!(a.shutdown().is_ok());
+}
+
+#[test]
+fn split_and_unsplit() {
+    let (a, b) = duplex(64);
+    let (rd, wr) = split(a);
+    let (wr, _) = write_all(wr, b"ping").wait().unwrap();
+    let (b, buf) = read_exact(b, [0; 4]).wait().unwrap();
+    assert_eq!(&buf, b"ping");
+
+    let (_b, _) = write_all(b, b"pong").wait().unwrap();
+    let (rd, buf) = read_exact(rd, [0; 4]).wait().unwrap();
+    assert_eq!(&buf, b"pong");
+
+    let a = rd.unsplit(wr);
+    assert_eq!(a.buffered_bytes(), 0);
+}
+
+#[test]
+#[should_panic]
+fn unsplit_unrelated_halves() {
+    let (a, b) = duplex(64);
+    let (rd, _) = split(a);
+    let (_, wr) = split(b);
+    rd.unsplit(wr);
+}
+
+#[test]
+fn copy_bidirectional_proxies_both_ways() {
+    let (client, proxy_a) = duplex(8);
+    let (proxy_b, server) = duplex(8);
+
+    let client = write_all(client, vec![1; 100])
+        .and_then(|(client, _)| shutdown(client))
+        .and_then(|client| read_to_end(client, Vec::new()));
+    let server = write_all(server, b"pong")
+        .and_then(|(server, _)| shutdown(server))
+        .and_then(|server| read_to_end(server, Vec::new()));
+    let proxy = copy_bidirectional(proxy_a, proxy_b);
+
+    let ((_, from_server), (_, from_client), (a_to_b, b_to_a, _, _)) =
+        client.join3(server, proxy).wait().unwrap();
+    assert_eq!(from_server, b"pong");
+    assert_eq!(from_client, vec![1; 100]);
+    assert_eq!((a_to_b, b_to_a), (100, 4));
+}
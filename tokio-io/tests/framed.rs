@@ -95,3 +95,45 @@ fn external_buf_does_not_shrink() {
     assert_eq!(readbuf.capacity(), INITIAL_CAPACITY * 2);
 }
 
+// Decodes frames of a configurable number of bytes
+struct ChunkCodec(usize);
+
+impl Decoder for ChunkCodec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+        if buf.len() < self.0 {
+            return Ok(None);
+        }
+        Ok(Some(buf.split_to(self.0)))
+    }
+}
+
+impl Encoder for ChunkCodec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn encode(&mut self, item: BytesMut, dst: &mut BytesMut) -> io::Result<()> {
+        dst.extend_from_slice(&item);
+        Ok(())
+    }
+}
+
+#[test]
+fn codec_mut_between_frames() {
+    let parts = FramedParts {
+        inner: DontReadIntoThis,
+        readbuf: b"\x03abcdef".to_vec().into(),
+        writebuf: BytesMut::with_capacity(0),
+    };
+    let mut framed = Framed::from_parts(parts, ChunkCodec(1));
+
+    let len = framed.by_ref().take(1).collect().wait().unwrap();
+    assert_eq!(len, vec![&b"\x03"[..]]);
+
+    framed.codec_mut().0 = len[0][0] as usize;
+    assert_eq!(framed.codec().0, 3);
+    let chunks = framed.take(2).collect().wait().unwrap();
+    assert_eq!(chunks, vec![&b"abc"[..], &b"def"[..]]);
+}
@@ -3,13 +3,14 @@ extern crate bytes;
 extern crate futures;
 
 use tokio_io::AsyncRead;
-use tokio_io::codec::{FramedRead, Decoder};
+use tokio_io::codec::{FramedBufRead, FramedRead, Decoder, LinesCodec, LinesCodecError,
+                      MultipartDecoder, MultipartError};
 
 use bytes::{BytesMut, Buf, IntoBuf, BigEndian};
 use futures::Stream;
 use futures::Async::{Ready, NotReady};
 
-use std::io::{self, Read};
+use std::io::{self, Cursor, Read};
 use std::collections::VecDeque;
 
 macro_rules! mock {
@@ -191,6 +192,136 @@ fn multi_frames_on_eof() {
     assert_eq!(Ready(None), framed.poll().unwrap());
 }
 
+#[test]
+fn short_frame_on_eof() {
+    // Accepts a final frame shorter than 4 bytes once the stream has closed.
+    struct MyDecoder;
+
+    impl Decoder for MyDecoder {
+        type Item = u32;
+        type Error = io::Error;
+
+        fn decode(&mut self, buf: &mut BytesMut) -> io::Result<Option<u32>> {
+            U32Decoder.decode(buf)
+        }
+
+        fn decode_eof(&mut self, buf: &mut BytesMut) -> io::Result<Option<u32>> {
+            if let Some(n) = U32Decoder.decode(buf)? {
+                return Ok(Some(n));
+            }
+            if buf.is_empty() {
+                return Ok(None);
+            }
+            let n = buf.take().iter().fold(0, |n, &b| n << 8 | b as u32);
+            Ok(Some(n))
+        }
+    }
+
+    let mock = mock! {
+        Ok(b"\x00\x00\x00\x01\x00".to_vec()),
+        Err(io::Error::new(io::ErrorKind::WouldBlock, "")),
+        Ok(b"\x02".to_vec()),
+    };
+
+    let mut framed = FramedRead::new(mock, MyDecoder);
+    assert_eq!(Ready(Some(1)), framed.poll().unwrap());
+    assert_eq!(NotReady, framed.poll().unwrap());
+    assert_eq!(Ready(Some(2)), framed.poll().unwrap());
+    assert_eq!(Ready(None), framed.poll().unwrap());
+}
+
+#[test]
+fn buf_read_frames() {
+    let rd = io::BufReader::with_capacity(3, Cursor::new(
+        b"\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00".to_vec()));
+
+    let mut framed = FramedBufRead::new(rd, U32Decoder);
+    assert_eq!(Ready(Some(0)), framed.poll().unwrap());
+    assert_eq!(Ready(Some(1)), framed.poll().unwrap());
+    assert!(framed.poll().is_err());
+
+    let (_, rest) = framed.into_parts();
+    assert_eq!(rest, b"\x00\x00"[..]);
+}
+
+#[test]
+fn read_exceeds_max_buf_size() {
+    let mock = mock! {
+        Ok(b"abcd".to_vec()),
+        Ok(b"ef\nghijk".to_vec()),
+    };
+
+    let mut framed = FramedRead::new(mock, LinesCodec::new().with_max_buf_size(4));
+    assert_eq!(Ready(Some("abcdef".to_string())), framed.poll().unwrap());
+    match framed.poll() {
+        Err(LinesCodecError::Io(ref e)) if e.kind() == io::ErrorKind::Other => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn buf_read_exceeds_max_buf_size() {
+    let rd = io::BufReader::with_capacity(3, Cursor::new(b"ab\ncdefg".to_vec()));
+
+    let mut framed = FramedBufRead::new(rd, LinesCodec::new().with_max_buf_size(4));
+    assert_eq!(Ready(Some("ab".to_string())), framed.poll().unwrap());
+    match framed.poll() {
+        Err(LinesCodecError::Io(ref e)) if e.kind() == io::ErrorKind::Other => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn read_with_capacity() {
+    let mock = mock! {
+        Ok(b"\x00\x00\x00\x00\x00\x00\x00\x01".to_vec()),
+    };
+
+    let mut framed = FramedRead::with_capacity(mock, U32Decoder, 64 * 1024);
+    assert!(framed.capacity() >= 64 * 1024);
+    assert_eq!(Ready(Some(0)), framed.poll().unwrap());
+
+    framed.reserve(128 * 1024);
+    assert!(framed.capacity() >= 128 * 1024);
+    assert_eq!(Ready(Some(1)), framed.poll().unwrap());
+    assert_eq!(Ready(None), framed.poll().unwrap());
+}
+
+#[test]
+fn read_into_parts() {
+    let mock = mock! {
+        Ok(b"\x00\x00\x00\x01\x00\x00".to_vec()),
+        Ok(b"raw".to_vec()),
+    };
+
+    let mut framed = FramedRead::new(mock, U32Decoder);
+    assert_eq!(Ready(Some(1)), framed.poll().unwrap());
+    let (mut mock, buf) = framed.into_parts();
+    assert_eq!(&buf[..], b"\x00\x00");
+
+    let mut rest = [0; 3];
+    assert_eq!(mock.read(&mut rest).unwrap(), 3);
+    assert_eq!(&rest, b"raw");
+}
+
+#[test]
+fn read_map_decoder() {
+    let mock = mock! {
+        Ok(b"\x00\x00\x00\x01\x00\x00\x00\x02\x00".to_vec()),
+        Ok(b"\x00\x00\x03\x00\x00\x00\x04".to_vec()),
+    };
+
+    let mut framed = FramedRead::new(mock, U32Decoder);
+    assert_eq!(Ready(Some(1)), framed.poll().unwrap());
+
+    // The partially read third frame is kept
+    let mut framed = framed.map_decoder(|n| n * 10)
+        .filter_map_decoder(|n| if n == 30 { None } else { Some(n) });
+    assert_eq!(Ready(Some(20)), framed.poll().unwrap());
+    assert_eq!(Ready(Some(40)), framed.poll().unwrap());
+    assert_eq!(Ready(None), framed.poll().unwrap());
+}
+
 // ===== Mock ======
 
 struct Mock {
@@ -213,3 +344,14 @@ impl Read for Mock {
 
 impl AsyncRead for Mock {
 }
+
+#[test]
+fn read_preserves_decoder_error_type() {
+    let data = b"--b\r\n\r\npart\r\n--b".to_vec();
+    let mut framed = FramedRead::new(Cursor::new(data), MultipartDecoder::new(b"b"));
+    assert_eq!(framed.poll().unwrap(), Ready(Some(BytesMut::from(&b"part"[..]))));
+    match framed.poll() {
+        Err(MultipartError::MissingBoundary) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
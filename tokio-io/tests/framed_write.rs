@@ -102,6 +102,149 @@ fn write_hits_backpressure() {
     assert_eq!(0, framed.get_ref().calls.len());
 }
 
+#[test]
+fn write_hits_custom_high_water_mark() {
+    let mock = mock! {
+        Err(io::Error::new(io::ErrorKind::WouldBlock, "not ready")),
+        Ok(b"\x00\x00\x00\x00\x00\x00\x00\x01".to_vec()),
+        Ok(b"\x00\x00\x00\x02".to_vec()),
+    };
+
+    let mut framed = FramedWrite::new(mock, U32Encoder);
+    framed.set_high_water_mark(8);
+    assert_eq!(8, framed.high_water_mark());
+
+    assert!(framed.start_send(0).unwrap().is_ready());
+    assert!(framed.start_send(1).unwrap().is_ready());
+
+    // The buffer is at the mark and can't be flushed yet
+    assert!(!framed.start_send(2).unwrap().is_ready());
+
+    assert!(framed.start_send(2).unwrap().is_ready());
+    assert!(framed.poll_complete().unwrap().is_ready());
+    assert_eq!(0, framed.get_ref().calls.len());
+}
+
+#[test]
+fn write_resumes_below_low_water_mark() {
+    let mock = mock! {
+        Ok(b"\x00\x00".to_vec()),
+        Err(io::Error::new(io::ErrorKind::WouldBlock, "not ready")),
+        Ok(b"\x00\x00".to_vec()),
+        Err(io::Error::new(io::ErrorKind::WouldBlock, "not ready")),
+        Ok(b"\x00\x00\x00\x01".to_vec()),
+        Ok(b"\x00\x00\x00\x02".to_vec()),
+    };
+
+    let mut framed = FramedWrite::new(mock, U32Encoder);
+    framed.set_high_water_mark(8);
+    framed.set_low_water_mark(4);
+    assert_eq!(4, framed.low_water_mark());
+
+    assert!(framed.start_send(0).unwrap().is_ready());
+    assert!(framed.start_send(1).unwrap().is_ready());
+
+    // Below the high-water mark but not below the low-water mark yet
+    assert!(!framed.start_send(2).unwrap().is_ready());
+    assert!(!framed.start_send(2).unwrap().is_ready());
+
+    assert!(framed.start_send(2).unwrap().is_ready());
+    assert!(framed.poll_complete().unwrap().is_ready());
+    assert_eq!(0, framed.get_ref().calls.len());
+
+    framed.set_low_water_mark(16);
+    assert_eq!(8, framed.low_water_mark());
+}
+
+#[test]
+fn write_with_zero_high_water_mark() {
+    let mock = mock! {
+        Err(io::Error::new(io::ErrorKind::WouldBlock, "not ready")),
+        Ok(b"\x00\x00\x00\x01".to_vec()),
+        Ok(b"\x00\x00\x00\x02".to_vec()),
+    };
+
+    let mut framed = FramedWrite::new(mock, U32Encoder);
+    framed.set_high_water_mark(0);
+
+    // An empty buffer still takes a frame
+    assert!(framed.start_send(1).unwrap().is_ready());
+    assert!(!framed.start_send(2).unwrap().is_ready());
+
+    assert!(framed.start_send(2).unwrap().is_ready());
+    assert!(framed.poll_complete().unwrap().is_ready());
+    assert_eq!(0, framed.get_ref().calls.len());
+}
+
+#[test]
+fn write_resumes_at_zero_low_water_mark() {
+    let mock = mock! {
+        Ok(b"\x00\x00".to_vec()),
+        Err(io::Error::new(io::ErrorKind::WouldBlock, "not ready")),
+        Ok(b"\x00\x00\x00\x00\x00\x01".to_vec()),
+        Ok(b"\x00\x00\x00\x02".to_vec()),
+    };
+
+    let mut framed = FramedWrite::new(mock, U32Encoder);
+    framed.set_high_water_mark(8);
+    framed.set_low_water_mark(0);
+
+    assert!(framed.start_send(0).unwrap().is_ready());
+    assert!(framed.start_send(1).unwrap().is_ready());
+    assert!(!framed.start_send(2).unwrap().is_ready());
+
+    // Resumes once the buffer has been drained completely
+    assert!(framed.start_send(2).unwrap().is_ready());
+    assert!(framed.poll_complete().unwrap().is_ready());
+    assert_eq!(0, framed.get_ref().calls.len());
+}
+
+#[test]
+fn write_into_parts() {
+    let mock = mock! {
+        Ok(b"\x00\x00\x00\x01".to_vec()),
+        Err(io::Error::new(io::ErrorKind::WouldBlock, "not ready")),
+    };
+
+    let mut framed = FramedWrite::new(mock, U32Encoder);
+    assert!(framed.start_send(1).unwrap().is_ready());
+    assert!(framed.start_send(2).unwrap().is_ready());
+    assert!(!framed.poll_complete().unwrap().is_ready());
+
+    let (mock, buf) = framed.into_parts();
+    assert_eq!(0, mock.calls.len());
+    assert_eq!(&buf[..], b"\x00\x00\x00\x02");
+}
+
+#[test]
+fn write_with_encoder() {
+    let mock = mock! {
+        Ok(b"\x00\x00\x00\x01\x00\x00\x00\x04".to_vec()),
+    };
+
+    let mut framed = FramedWrite::new(mock, U32Encoder);
+    framed.set_high_water_mark(16);
+    assert!(framed.start_send(1).unwrap().is_ready());
+
+    let mut framed = framed.with_encoder(|s: &str| s.len() as u32);
+    assert_eq!(16, framed.high_water_mark());
+    assert!(framed.start_send("four").unwrap().is_ready());
+    assert!(framed.poll_complete().unwrap().is_ready());
+    assert_eq!(0, framed.get_ref().calls.len());
+}
+
+#[test]
+fn write_sink_with_still_available() {
+    let mock = mock! {
+        Ok(b"\x00\x00\x00\x05".to_vec()),
+    };
+
+    let mut framed = FramedWrite::new(mock, U32Encoder)
+        .with(|n: u32| Ok::<_, io::Error>(n + 1));
+    assert!(framed.start_send(4).unwrap().is_ready());
+    assert!(framed.poll_complete().unwrap().is_ready());
+}
+
 // ===== Mock ======
 
 struct Mock {
@@ -0,0 +1,27 @@
+#![cfg(feature = "digest")]
+
+extern crate tokio_io;
+extern crate futures;
+extern crate sha2;
+
+use tokio_io::io::{read_to_end, read_until, HashRead};
+use futures::Future;
+use sha2::{Digest, Sha256};
+
+use std::io::{self, BufRead, Cursor};
+
+#[test]
+fn hash_consumed_bytes() {
+    let rd = io::BufReader::with_capacity(3, Cursor::new(b"abc\ndef".to_vec()));
+    let mut rd = HashRead::<_, Sha256>::new(rd);
+
+    assert_eq!(rd.fill_buf().unwrap(), b"abc");
+    assert_eq!(rd.fill_buf().unwrap(), b"abc");
+    rd.consume(1);
+
+    let (rd, _) = read_until(rd, b'\n', Vec::new()).wait().unwrap();
+    let (rd, _) = read_to_end(rd, Vec::new()).wait().unwrap();
+
+    let (_, hash) = rd.finalize();
+    assert_eq!(hash, Sha256::digest(b"abc\ndef"));
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "json")]
+
+extern crate tokio_io;
+extern crate bytes;
+#[macro_use]
+extern crate serde_json;
+
+use bytes::{BytesMut, BufMut};
+use serde_json::Value;
+use tokio_io::codec::{JsonCodec, JsonCodecError, Decoder, Encoder};
+
+#[test]
+fn json_decoder() {
+    let mut codec = JsonCodec::<Value>::new();
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("{\"a\":1}\n\n[true, null]\r\n\"partial");
+    assert_eq!(json!({"a": 1}), codec.decode(buf).unwrap().unwrap());
+    assert_eq!(json!([true, null]), codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put("\"\n");
+    assert_eq!(json!("partial"), codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put("42");
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!(json!(42), codec.decode_eof(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode_eof(buf).unwrap());
+}
+
+#[test]
+fn json_decoder_invalid() {
+    let mut codec = JsonCodec::<Value>::new();
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("{oops}\n1\n");
+    match codec.decode(buf) {
+        Err(JsonCodecError::Json(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(json!(1), codec.decode(buf).unwrap().unwrap());
+}
+
+#[test]
+fn json_encoder() {
+    let mut codec = JsonCodec::new();
+    let mut buf = BytesMut::new();
+
+    codec.encode(json!({"a": [1, 2]}), &mut buf).unwrap();
+    codec.encode(json!("b"), &mut buf).unwrap();
+    assert_eq!(&buf[..], &b"{\"a\":[1,2]}\n\"b\"\n"[..]);
+}
@@ -1,9 +1,13 @@
 extern crate tokio_io;
+extern crate bytes;
 extern crate futures;
 
 use tokio_io::{AsyncRead, AsyncWrite};
+use tokio_io::codec::{Decoder, Encoder};
 use tokio_io::codec::length_delimited::*;
 
+use bytes::{Bytes, BytesMut};
+
 use futures::{Stream, Sink, Poll};
 use futures::Async::*;
 
@@ -468,6 +472,39 @@ fn write_update_max_frame_len_in_flight() {
     assert!(io.get_ref().calls.is_empty());
 }
 
+#[test]
+fn codec_round_trip() {
+    let mut codec = LengthDelimitedCodec::builder()
+        .length_field_length(2)
+        .little_endian()
+        .new_codec();
+
+    let mut buf = BytesMut::new();
+    codec.encode(Bytes::from_static(b"abcdefghi"), &mut buf).unwrap();
+    assert_eq!(buf, b"\x09\x00abcdefghi"[..]);
+
+    let mut partial = buf.split_to(5);
+    assert_eq!(codec.decode(&mut partial).unwrap(), None);
+    partial.unsplit(buf);
+    assert_eq!(codec.decode(&mut partial).unwrap().unwrap(), b"abcdefghi"[..]);
+    assert!(partial.is_empty());
+}
+
+#[test]
+fn codec_max_frame_len() {
+    let mut codec = LengthDelimitedCodec::builder()
+        .max_frame_length(5)
+        .new_codec();
+
+    let mut buf = BytesMut::new();
+    let err = codec.encode(Bytes::from_static(b"abcdef"), &mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let mut buf = BytesMut::from(&b"\x00\x00\x00\x06abcdef"[..]);
+    let err = codec.decode(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
 // ===== Test utils =====
 
 fn would_block() -> io::Error {
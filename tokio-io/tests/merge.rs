@@ -0,0 +1,42 @@
+extern crate tokio_io;
+extern crate futures;
+
+use tokio_io::io::{merge, pipe};
+use futures::{Async, Future, Stream};
+use futures::future::poll_fn;
+
+use std::io::{self, Cursor, Write};
+
+#[test]
+fn merge_prefers_first() {
+    let items = merge(Cursor::new(b"ab".to_vec()), Cursor::new(b"cd".to_vec()))
+        .collect().wait().unwrap();
+    assert_eq!(items, vec!["ab", "cd"]);
+
+    let items = merge(Cursor::new(b"ab".to_vec()), Cursor::new(b"cd".to_vec()))
+        .prefer_second()
+        .collect().wait().unwrap();
+    assert_eq!(items, vec!["cd", "ab"]);
+}
+
+#[test]
+fn merge_by_readiness() {
+    let (mut wr_a, rd_a) = pipe(16);
+    let (mut wr_b, rd_b) = pipe(16);
+    let mut merged = merge(rd_a, rd_b);
+
+    let mut poll = || poll_fn(|| Ok::<_, io::Error>(Async::Ready(merged.poll()))).wait().unwrap();
+    assert!(poll().unwrap().is_not_ready());
+
+    wr_b.write_all(b"from b").unwrap();
+    assert_eq!(poll().unwrap(), Async::Ready(Some("from b".into())));
+    assert!(poll().unwrap().is_not_ready());
+
+    wr_a.write_all(b"from a").unwrap();
+    drop(wr_b);
+    assert_eq!(poll().unwrap(), Async::Ready(Some("from a".into())));
+    assert!(poll().unwrap().is_not_ready());
+
+    drop(wr_a);
+    assert_eq!(poll().unwrap(), Async::Ready(None));
+}
@@ -0,0 +1,67 @@
+extern crate tokio_io;
+extern crate futures;
+
+use tokio_io::AsyncWrite;
+use tokio_io::codec::{FramedRead, FramedWrite, LinesCodec};
+use tokio_io::io::{pipe, read_to_end, read_until, shutdown, write_all};
+use futures::{stream, Async, Future, Sink, Stream};
+use futures::future::poll_fn;
+
+use std::io::{self, BufRead, Write};
+
+#[test]
+fn writes_block_when_full() {
+    let (wr, rd) = pipe(4);
+    let (mut wr, _) = write_all(wr, b"abcd").wait().unwrap();
+
+    // Still full, so a write would block
+    let res = poll_fn(|| Ok::<_, io::Error>(Async::Ready(wr.poll_write(b"e"))))
+        .wait().unwrap();
+    assert!(res.unwrap().is_not_ready());
+
+    assert_eq!(rd.buffered_bytes(), 4);
+    let (rd, buf) = read_until(rd, b'c', Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"abc");
+    assert_eq!(rd.buffered_bytes(), 1);
+    let (wr, _) = write_all(wr, b"efg").wait().unwrap();
+    let _wr = shutdown(wr).wait().unwrap();
+    let (_, rest) = read_to_end(rd, Vec::new()).wait().unwrap();
+    assert_eq!(rest, b"defg");
+}
+
+#[test]
+fn large_transfer() {
+    let (wr, rd) = pipe(7);
+    let data = (0..10_000).map(|i| i as u8).collect::<Vec<_>>();
+    let write = write_all(wr, data.clone()).map(drop);
+    let read = read_to_end(rd, Vec::new());
+    let (_, (_, buf)) = write.join(read).wait().unwrap();
+    assert_eq!(buf, data);
+}
+
+#[test]
+fn empty_pipe_blocks_fill_buf() {
+    let (_wr, mut rd) = pipe(4);
+    let res = poll_fn(|| Ok::<_, io::Error>(Async::Ready(rd.fill_buf().map(|b| b.len()))))
+        .wait().unwrap();
+    assert_eq!(res.unwrap_err().kind(), io::ErrorKind::WouldBlock);
+}
+
+#[test]
+fn write_after_reader_dropped() {
+    let (mut wr, rd) = pipe(4);
+    drop(rd);
+    assert_eq!(wr.write(b"x").unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn framed_over_pipe() {
+    let (wr, rd) = pipe(3);
+    let lines = vec!["hello".to_string(), "pipe".to_string(), String::new()];
+
+    let send = FramedWrite::new(wr, LinesCodec::new())
+        .send_all(stream::iter_ok::<_, io::Error>(lines.clone()));
+    let recv = FramedRead::new(rd, LinesCodec::new()).collect();
+    let (_, received) = send.join(recv).wait().unwrap();
+    assert_eq!(received, lines);
+}
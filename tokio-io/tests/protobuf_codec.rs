@@ -0,0 +1,27 @@
+#![cfg(feature = "prost")]
+
+extern crate tokio_io;
+extern crate bytes;
+
+use bytes::{BufMut, BytesMut};
+use tokio_io::codec::{Decoder, Encoder, ProtobufCodec, ProtobufCodecError};
+
+#[test]
+fn protobuf_round_trip() {
+    let mut codec = ProtobufCodec::<String>::new();
+    let mut buf = BytesMut::new();
+    codec.encode("hi".to_string(), &mut buf).unwrap();
+    assert_eq!(&buf[..], &b"\x04\x0a\x02hi"[..]);
+    codec.encode(String::new(), &mut buf).unwrap();
+
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some("hi".to_string()));
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some(String::new()));
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+
+    buf.reserve(4);
+    buf.put_slice(b"\x03\x0a\x05h");
+    match codec.decode(&mut buf) {
+        Err(ProtobufCodecError::DecodeError(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
@@ -0,0 +1,69 @@
+extern crate tokio_io;
+extern crate futures;
+
+use tokio_io::io::{read_frame, read_i16_le, read_i8, read_pascal_string,
+                   read_pascal_string_u16_be, read_u8, read_u16_be, read_u32_be, read_u32_le,
+                   read_u64_le};
+use futures::Future;
+
+use std::io::{self, BufReader, Cursor};
+
+#[test]
+fn read_integers() {
+    let mut data = vec![0x01, 0xff, 0x12, 0x34, 0xfe, 0xff];
+    data.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef, 0xef, 0xbe, 0xad, 0xde]);
+    data.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0x80]);
+    let rd = BufReader::with_capacity(3, Cursor::new(data));
+
+    let (rd, v) = read_u8(rd).wait().unwrap();
+    assert_eq!(v, 1);
+    let (rd, v) = read_i8(rd).wait().unwrap();
+    assert_eq!(v, -1);
+    let (rd, v) = read_u16_be(rd).wait().unwrap();
+    assert_eq!(v, 0x1234);
+    let (rd, v) = read_i16_le(rd).wait().unwrap();
+    assert_eq!(v, -2);
+    let (rd, v) = read_u32_be(rd).wait().unwrap();
+    assert_eq!(v, 0xdeadbeef);
+    let (rd, v) = read_u32_le(rd).wait().unwrap();
+    assert_eq!(v, 0xdeadbeef);
+    let (rd, v) = read_u64_le(rd).wait().unwrap();
+    assert_eq!(v, 0x8000_0000_0000_0001);
+
+    let err = read_u8(rd).wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn read_pascal_strings() {
+    let rd = BufReader::with_capacity(2, Cursor::new(&b"\x03abc\x00\x00\x02hi\x05ab"[..]));
+
+    let (rd, s) = read_pascal_string(rd).wait().unwrap();
+    assert_eq!(s, b"abc");
+    let (rd, s) = read_pascal_string(rd).wait().unwrap();
+    assert_eq!(s, b"");
+    let (rd, s) = read_pascal_string_u16_be(rd).wait().unwrap();
+    assert_eq!(s, b"hi");
+
+    let err = read_pascal_string(rd).wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn read_frames() {
+    let rd = Cursor::new(b"\x01\x00\x03abc\x02\x02\x00hi".to_vec());
+    let (rd, header, body) = read_frame(rd, [0; 3], 1, 2).wait().unwrap();
+    assert_eq!(header, [1, 0, 3]);
+    assert_eq!(body, b"abc");
+
+    let (rd, header, body) = read_frame(rd, [0; 3], 1, 2).little_endian().wait().unwrap();
+    assert_eq!(header, [2, 2, 0]);
+    assert_eq!(body, b"hi");
+
+    let err = read_frame(rd, [0; 3], 1, 2).wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+    let rd = Cursor::new(b"\x05abc".to_vec());
+    let err = read_frame(rd, [0; 1], 0, 1).wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
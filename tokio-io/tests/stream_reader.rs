@@ -0,0 +1,48 @@
+extern crate tokio_io;
+extern crate bytes;
+extern crate futures;
+
+use bytes::Bytes;
+use futures::{stream, Future, Stream};
+use tokio_io::io::{lines, read_exact, StreamReader};
+
+use std::io::{self, BufRead, Read};
+
+fn chunks(chunks: &[&'static str]) -> Vec<Result<Bytes, io::Error>> {
+    chunks.iter().map(|c| Ok(Bytes::from_static(c.as_bytes()))).collect()
+}
+
+#[test]
+fn reads_across_chunks() {
+    let rd = StreamReader::new(stream::iter_result(chunks(&["one\ntw", "", "o\nthr", "ee"])));
+
+    let lines = lines(rd).collect().wait().unwrap();
+    assert_eq!(lines, vec!["one".to_string(), "two".to_string(), "three".to_string()]);
+}
+
+#[test]
+fn partial_consume() {
+    let mut rd = StreamReader::new(stream::iter_result(chunks(&["abcd", "ef"])));
+
+    assert_eq!(rd.fill_buf().unwrap(), b"abcd");
+    rd.consume(1);
+    assert_eq!(rd.buffer(), b"bcd");
+    let (mut rd, buf) = read_exact(rd, [0; 4]).wait().unwrap();
+    assert_eq!(&buf, b"bcde");
+
+    let mut rest = Vec::new();
+    rd.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"f");
+    assert_eq!(rd.fill_buf().unwrap(), b"");
+}
+
+#[test]
+fn stream_errors() {
+    let mut items = chunks(&["ab"]);
+    items.push(Err(io::Error::new(io::ErrorKind::Other, "boom")));
+    let mut rd = StreamReader::new(stream::iter_result(items));
+
+    let mut buf = [0; 4];
+    assert_eq!(rd.read(&mut buf).unwrap(), 2);
+    assert_eq!(rd.read(&mut buf).unwrap_err().kind(), io::ErrorKind::Other);
+}
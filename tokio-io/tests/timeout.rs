@@ -0,0 +1,118 @@
+extern crate tokio_io;
+extern crate futures;
+
+use tokio_io::io::{read_until, Clock, Timeout};
+use futures::{future, Future};
+use futures::future::{Empty, FutureResult};
+
+use std::io::{self, BufRead, Cursor, Read};
+use std::time::Duration;
+
+// A clock whose delays have always elapsed.
+#[derive(Debug, Default)]
+struct Expired;
+
+impl Clock for Expired {
+    type Delay = FutureResult<(), io::Error>;
+
+    fn delay(&mut self, _: Duration) -> Self::Delay {
+        future::ok(())
+    }
+}
+
+// A clock whose delays never elapse.
+#[derive(Debug, Default)]
+struct Never;
+
+impl Clock for Never {
+    type Delay = Empty<(), io::Error>;
+
+    fn delay(&mut self, _: Duration) -> Self::Delay {
+        future::empty()
+    }
+}
+
+// Yields the data once and then blocks forever.
+#[derive(Debug)]
+struct Blocking(Option<Vec<u8>>);
+
+impl Read for Blocking {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.0.take() {
+            Some(data) => {
+                buf[..data.len()].copy_from_slice(&data);
+                Ok(data.len())
+            }
+            None => Err(io::Error::new(io::ErrorKind::WouldBlock, "")),
+        }
+    }
+}
+
+impl tokio_io::AsyncRead for Blocking {}
+
+#[test]
+fn timeout_ready_reads_pass_through() {
+    let mut rd = Timeout::<_, Expired>::new(Cursor::new(b"abc".to_vec()),
+                                            Duration::from_secs(1));
+    let mut buf = [0; 8];
+    assert_eq!(rd.read(&mut buf).unwrap(), 3);
+    assert_eq!(rd.read(&mut buf).unwrap(), 0);
+    assert_eq!(rd.duration(), Duration::from_secs(1));
+}
+
+#[test]
+fn timeout_blocked_read_times_out() {
+    let mut rd = Timeout::<_, Expired>::new(Blocking(Some(b"ab".to_vec())),
+                                            Duration::from_secs(1));
+    let mut buf = [0; 8];
+    assert_eq!(rd.read(&mut buf).unwrap(), 2);
+    assert_eq!(rd.read(&mut buf).unwrap_err().kind(), io::ErrorKind::TimedOut);
+}
+
+#[test]
+fn timeout_pending_delay_would_block() {
+    let mut rd = Timeout::with_clock(Blocking(None), Duration::from_secs(1), Never);
+    let mut buf = [0; 8];
+    assert_eq!(rd.read(&mut buf).unwrap_err().kind(), io::ErrorKind::WouldBlock);
+    assert!(format!("{:?}", rd).contains("is_delayed: true"));
+}
+
+#[test]
+fn timeout_buf_read() {
+    let rd = io::BufReader::new(Blocking(Some(b"a\nb".to_vec())));
+    let mut rd = Timeout::<_, Expired>::new(rd, Duration::from_secs(1));
+    assert_eq!(rd.fill_buf().unwrap(), b"a\nb");
+
+    let (rd, buf) = read_until(rd, b'\n', Vec::new()).wait().unwrap();
+    assert_eq!(buf, b"a\n");
+
+    let err = read_until(rd, b'\n', Vec::new()).wait().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+}
+
+#[test]
+fn timeout_fill_buf_calls_inner_once() {
+    // Counts the calls to `fill_buf`, which is always at EOF.
+    struct Counting(usize);
+
+    impl Read for Counting {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Ok(0)
+        }
+    }
+
+    impl BufRead for Counting {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            self.0 += 1;
+            Ok(&[])
+        }
+
+        fn consume(&mut self, _: usize) {}
+    }
+
+    impl tokio_io::AsyncRead for Counting {}
+
+    let mut rd = Timeout::<_, Never>::new(Counting(0), Duration::from_secs(1));
+    assert!(rd.fill_buf().unwrap().is_empty());
+    assert_eq!(rd.get_ref().0, 1);
+}